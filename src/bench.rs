@@ -0,0 +1,188 @@
+use crate::*;
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fs::{self, File},
+    io::{Read, Write},
+    path::Path,
+    time::Duration,
+};
+
+#[derive(clap::Args)]
+pub(crate) struct BenchArgs {
+    /// The number of times to run each solver
+    #[clap(long = "runs", short = 'n')]
+    #[clap(default_value = "10", value_parser = parse_runs)]
+    runs: usize,
+
+    /// The baseline file to compare against (and to save to with --save)
+    #[clap(long = "baseline", short = 'b')]
+    #[clap(default_value = "bench_baseline")]
+    baseline: String,
+
+    /// Save the results as the new baseline
+    #[clap(long = "save", short = 's')]
+    save: bool,
+
+    /// The increase in median time (in percent) above which a day is flagged as slower
+    #[clap(long = "threshold")]
+    #[clap(default_value = "10")]
+    threshold: f64,
+}
+
+fn parse_runs(s: &str) -> Result<usize, String> {
+    clap_num::number_range(s, 1, usize::MAX)
+}
+
+// What a baseline was measured for: the year, the day, the part if only one
+// part was solved, and the parameters as `name=value` separated by spaces, as
+// the time is only comparable between runs that do the same work.
+type Key = (usize, usize, Option<usize>, String);
+
+fn key(year: usize, day: usize, part: Option<usize>, params: &Params) -> Key {
+    let params = params
+        .iter()
+        .map(|(name, value)| format!("{name}={value}"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    (year, day, part, params)
+}
+
+#[derive(Clone, Copy)]
+struct Stats {
+    min: Duration,
+    median: Duration,
+    p95: Duration,
+    max: Duration,
+}
+
+impl Stats {
+    fn new(mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+        let n = samples.len();
+        let median = if n % 2 == 1 {
+            samples[n / 2]
+        } else {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        };
+        // Nearest-rank percentile.
+        let p95 = samples[(n * 95).div_ceil(100) - 1];
+        Stats {
+            min: samples[0],
+            median,
+            p95,
+            max: samples[n - 1],
+        }
+    }
+
+    fn to_line(self, (year, day, part, params): &Key) -> String {
+        let part = part.map_or("-".to_owned(), |part| part.to_string());
+        let line = format!(
+            "{year} {day} {part} {} {} {} {} {params}",
+            self.min.as_nanos(),
+            self.median.as_nanos(),
+            self.p95.as_nanos(),
+            self.max.as_nanos()
+        );
+        line.trim_end().to_owned()
+    }
+
+    fn from_line(line: &str) -> Option<(Key, Self)> {
+        // The parameters come last, as their values may contain spaces.
+        let parts = line.splitn(8, ' ').collect::<Vec<_>>();
+        if parts.len() < 7 {
+            return None;
+        }
+        let part = match parts[2] {
+            "-" => None,
+            part => Some(part.parse().ok()?),
+        };
+        let params = parts.get(7).copied().unwrap_or_default().to_owned();
+        let nanos = |s: &str| s.parse::<u64>().ok().map(Duration::from_nanos);
+        Some((
+            (parts[0].parse().ok()?, parts[1].parse().ok()?, part, params),
+            Stats {
+                min: nanos(parts[3])?,
+                median: nanos(parts[4])?,
+                p95: nanos(parts[5])?,
+                max: nanos(parts[6])?,
+            },
+        ))
+    }
+}
+
+fn read_baseline(path: &Path) -> Result<BTreeMap<Key, Stats>, Box<dyn Error>> {
+    let mut baseline = BTreeMap::new();
+    for (i, line) in fs::read_to_string(path)?.lines().enumerate() {
+        let (key, stats) =
+            Stats::from_line(line).ok_or_else(|| format!("malformed baseline {path:?} at line {}", i + 1))?;
+        baseline.insert(key, stats);
+    }
+    Ok(baseline)
+}
+
+fn write_baseline(path: &Path, results: &BTreeMap<Key, Stats>) -> Result<(), Box<dyn Error>> {
+    let mut file = File::create(path)?;
+    for (key, &stats) in results.iter() {
+        writeln!(file, "{}", stats.to_line(key))?;
+    }
+    Ok(())
+}
+
+pub(crate) fn run(
+    year: usize,
+    days: &[usize],
+    inputs: Vec<Input>,
+    params: &[Params],
+    part: Option<usize>,
+    args: &BenchArgs,
+) -> Result<(), Box<dyn Error>> {
+    let baseline_path = Path::new(&args.baseline);
+    let baseline = if baseline_path.is_file() {
+        read_baseline(baseline_path)?
+    } else {
+        BTreeMap::new()
+    };
+    let mut results = BTreeMap::new();
+    println!(
+        "{:<5} {:>12} {:>12} {:>12} {:>12}  Baseline median",
+        "Day", "Min", "Median", "P95", "Max"
+    );
+    for ((&day, input), params) in days.iter().zip(inputs).zip(params) {
+        // Read the whole input up front, so that only the solver itself is measured.
+        let mut data = String::new();
+        input.open()?.read_to_string(&mut data)?;
+        let mut samples = Vec::with_capacity(args.runs);
+        for _ in 0..args.runs {
+            let (_, timings) =
+                solve_with_params(year, day, data.as_bytes(), part, params, &CancellationToken::default())?;
+            samples.push(timings.total());
+        }
+        let stats = Stats::new(samples);
+        let key = key(year, day, part, params);
+        let comparison = match baseline.get(&key) {
+            Some(base) => {
+                let change = (stats.median.as_secs_f64() / base.median.as_secs_f64() - 1.0) * 100.0;
+                let flag = if change > args.threshold { "  SLOWER" } else { "" };
+                format!("{:.2?} ({change:+.1}%){flag}", base.median)
+            }
+            None => "-".to_owned(),
+        };
+        println!(
+            "{day:<5} {:>12} {:>12} {:>12} {:>12}  {comparison}",
+            format!("{:.2?}", stats.min),
+            format!("{:.2?}", stats.median),
+            format!("{:.2?}", stats.p95),
+            format!("{:.2?}", stats.max),
+        );
+        results.insert(key, stats);
+    }
+    if args.save {
+        // Keep the baseline for days, parts and parameters that were not part of
+        // this run.
+        let mut new_baseline = baseline;
+        new_baseline.extend(results);
+        write_baseline(baseline_path, &new_baseline)?;
+    }
+    Ok(())
+}
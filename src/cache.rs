@@ -0,0 +1,167 @@
+use crate::*;
+use serde::{Deserialize, Serialize};
+use std::{
+    env,
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+};
+
+#[derive(clap::Subcommand)]
+pub(crate) enum CacheCommand {
+    /// Remove all the cached answers
+    Clear,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    part_1: Option<Answer>,
+    part_2: Option<Answer>,
+}
+
+// The answers of earlier runs, stored in a file per day, input and build of this
+// binary. Including the build means that any change to a solver makes its old
+// answers unreachable, so they never need to be invalidated.
+pub(crate) struct Cache {
+    dir: PathBuf,
+    build_id: String,
+}
+
+impl Cache {
+    pub fn open(dir: &Path) -> Result<Self, Box<dyn Error>> {
+        let exe = env::current_exe()?;
+        let build_id = hash::hash(
+            &fs::read(&exe).map_err(|err| format!("cannot read {} to identify the build: {err}", exe.display()))?,
+        );
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            build_id,
+        })
+    }
+
+    // The key of the answers of a day for an input, which also covers which
+    // parts are solved and the parameters of the day.
+    pub fn key(&self, year: usize, day: usize, part: Option<usize>, params: &Params, input: &[u8]) -> String {
+        let mut data = format!("{}\n{year}\n{day}\n{part:?}\n", self.build_id);
+        for (name, value) in params.iter() {
+            data.push_str(&format!("{name}={value}\n"));
+        }
+        let mut data = data.into_bytes();
+        data.push(0);
+        data.extend_from_slice(input);
+        hash::hash(&data)
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{key}.json"))
+    }
+
+    // The cached answers, treating an unreadable entry like a missing one.
+    pub fn get(&self, key: &str) -> Option<Solution> {
+        let entry = serde_json::from_str::<Entry>(&fs::read_to_string(self.path(key)).ok()?).ok()?;
+        Some((entry.part_1, entry.part_2))
+    }
+
+    pub fn put(&self, key: &str, (part_1, part_2): &Solution) -> Result<(), Box<dyn Error>> {
+        fs::create_dir_all(&self.dir)?;
+        let entry = Entry {
+            part_1: part_1.clone(),
+            part_2: part_2.clone(),
+        };
+        // Write to a temporary file first, so that a day solved by two runs at
+        // once never leaves a half-written entry behind.
+        let tmp_path = self.dir.join(format!("{key}.{}.part", std::process::id()));
+        fs::write(&tmp_path, serde_json::to_string(&entry)?)?;
+        fs::rename(&tmp_path, self.path(key))?;
+        Ok(())
+    }
+}
+
+// Whether a file name is the key of an entry, which is a hash as written by
+// `hash::hash`.
+fn is_key(name: &str) -> bool {
+    name.len() == 16 && name.bytes().all(|byte| matches!(byte, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+// Whether a file is an entry written by `Cache::put`, and whether it is a
+// temporary file left behind by an interrupted one. Only those are removed when
+// clearing, so that a wrong `--cache-dir` never deletes anything else.
+fn is_cache_file(name: &str) -> (bool, bool) {
+    let entry = name.strip_suffix(".json").is_some_and(is_key);
+    let temporary = name
+        .strip_suffix(".part")
+        .and_then(|name| name.split_once('.'))
+        .is_some_and(|(key, pid)| is_key(key) && !pid.is_empty() && pid.bytes().all(|byte| byte.is_ascii_digit()));
+    (entry, temporary)
+}
+
+pub(crate) fn run(dir: &Path, command: &CacheCommand) -> Result<(), Box<dyn Error>> {
+    match command {
+        CacheCommand::Clear => {
+            let mut count = 0;
+            if dir.is_dir() {
+                for entry in fs::read_dir(dir)? {
+                    let entry = entry?;
+                    let (is_entry, is_temporary) = is_cache_file(&entry.file_name().to_string_lossy());
+                    if (is_entry || is_temporary) && entry.file_type()?.is_file() {
+                        fs::remove_file(entry.path())?;
+                        count += usize::from(is_entry);
+                    }
+                }
+            }
+            match count {
+                0 => println!("The cache at {} is already empty", dir.display()),
+                _ => println!("Removed {count} cached answer(s) from {}", dir.display()),
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cache(name: &str) -> Cache {
+        let dir = env::temp_dir().join(format!("aoc_cache_test_{name}_{}", std::process::id()));
+        _ = fs::remove_dir_all(&dir);
+        Cache::open(&dir).unwrap()
+    }
+
+    #[test]
+    fn round_trips_answers_exactly() {
+        let cache = cache("round_trip");
+        let solution = (
+            Some(Answer::Text("123".to_owned())),
+            Some(Answer::Image(vec!["#..#".to_owned(), ".##.".to_owned()])),
+        );
+        cache.put("0123456789abcdef", &solution).unwrap();
+        assert_eq!(cache.get("0123456789abcdef"), Some(solution));
+        assert_eq!(cache.get("fedcba9876543210"), None);
+        fs::remove_dir_all(&cache.dir).unwrap();
+    }
+
+    #[test]
+    fn key_covers_part_and_params() {
+        let cache = cache("key");
+        let params = Params::default();
+        let key = cache.key(2022, 15, None, &params, b"input");
+        assert_eq!(key, cache.key(2022, 15, None, &params, b"input"));
+        assert_ne!(key, cache.key(2022, 15, Some(1), &params, b"input"));
+        assert_ne!(
+            key,
+            cache.key(2022, 15, None, &[("row", "10")].into_iter().collect(), b"input")
+        );
+        assert_ne!(key, cache.key(2022, 14, None, &params, b"input"));
+        assert_ne!(key, cache.key(2022, 15, None, &params, b"other input"));
+    }
+
+    #[test]
+    fn clear_only_removes_cache_files() {
+        assert_eq!(is_cache_file("0123456789abcdef.json"), (true, false));
+        assert_eq!(is_cache_file("0123456789abcdef.123.part"), (false, true));
+        assert_eq!(is_cache_file("notes.txt"), (false, false));
+        assert_eq!(is_cache_file("0123456789ABCDEF.json"), (false, false));
+        assert_eq!(is_cache_file("0123456789abcdef.part"), (false, false));
+    }
+}
@@ -0,0 +1,90 @@
+use adventofcode2022::Answer;
+use serde::Serialize;
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    fs,
+    path::{Path, PathBuf},
+};
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub(crate) enum Status {
+    Pass,
+    Fail,
+    Missing,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Pass => write!(f, "PASS"),
+            Status::Fail => write!(f, "FAIL"),
+            Status::Missing => write!(f, "MISSING"),
+        }
+    }
+}
+
+// The expected answers are stored next to the input file, in a file named like
+// the input with "input" replaced by "answers" (e.g. `input_test` =>
+// `answers_test`). Other input files use the input file name with an
+// `.answers` extension.
+pub(crate) fn answers_path(input: &Path) -> PathBuf {
+    let name = input.file_name().unwrap_or_default().to_string_lossy();
+    match name.strip_prefix("input") {
+        Some(suffix) => input.with_file_name(format!("answers{suffix}")),
+        None => input.with_file_name(format!("{name}.answers")),
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct Answers([Option<Answer>; 2]);
+
+impl Answers {
+    // Reads answers in the same format as the text output, i.e. lines like
+    // `Part 1: <answer>` with any further lines of a multi-line answer
+    // following it. A missing file has no expected answers.
+    pub fn read(path: &Path) -> Result<Self, Box<dyn Error>> {
+        if !path.is_file() {
+            return Ok(Default::default());
+        }
+        let mut answers: [Option<String>; 2] = Default::default();
+        let mut current = None;
+        for (i, line) in fs::read_to_string(path)?.lines().enumerate() {
+            let header = line
+                .strip_prefix("Part ")
+                .and_then(|rest| rest.split_once(':'))
+                .and_then(|(part, answer)| Some((part.parse::<usize>().ok()?, answer)));
+            match (header, current) {
+                (Some((part @ (1 | 2), answer)), _) => {
+                    answers[part - 1] = Some(answer.trim().to_owned());
+                    current = Some(part - 1);
+                }
+                (None, Some(part)) if !line.trim().is_empty() => {
+                    let answer = answers[part].get_or_insert_with(String::new);
+                    if !answer.is_empty() {
+                        answer.push('\n');
+                    }
+                    answer.push_str(line.trim());
+                }
+                (None, _) if line.trim().is_empty() => (),
+                _ => return Err(format!("malformed answers file {path:?} at line {}", i + 1).into()),
+            }
+        }
+        let parse = |answer: Option<String>| answer.map(|answer| answer.parse()).transpose();
+        let [part_1, part_2] = answers;
+        Ok(Answers([parse(part_1)?, parse(part_2)?]))
+    }
+
+    pub fn expected(&self, part: usize) -> Option<&Answer> {
+        self.0[part - 1].as_ref()
+    }
+
+    pub fn check(&self, part: usize, answer: &Answer) -> Status {
+        match self.expected(part) {
+            Some(expected) if expected == answer => Status::Pass,
+            Some(_) => Status::Fail,
+            None => Status::Missing,
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use std::{
+    env,
+    error::Error,
+    fs,
+    path::PathBuf,
+    thread,
+    time::{Duration, Instant},
+};
+
+const USER_AGENT: &str = concat!(
+    "adventofcode2022/",
+    env!("CARGO_PKG_VERSION"),
+    " (+https://github.com/right-mouse/adventofcode2022)"
+);
+
+// The environment variable with the session cookie, which takes precedence over
+// the session file.
+const SESSION_VAR: &str = "AOC_SESSION";
+
+#[derive(clap::Args)]
+pub(crate) struct ClientArgs {
+    /// The base URL of the Advent of Code website
+    #[clap(long = "base-url")]
+    #[clap(default_value = "https://adventofcode.com")]
+    base_url: String,
+
+    /// The file with the session cookie, used if AOC_SESSION is not set
+    #[clap(long = "session-file")]
+    #[clap(default_value = ".aoc_session")]
+    session_file: PathBuf,
+
+    /// The minimum time in seconds between two requests
+    #[clap(long = "throttle")]
+    #[clap(default_value = "5", value_parser = parse_throttle)]
+    throttle: Duration,
+}
+
+fn parse_throttle(s: &str) -> Result<Duration, String> {
+    let seconds = s.parse::<f64>().map_err(|err| err.to_string())?;
+    Duration::try_from_secs_f64(seconds).map_err(|_| format!("{s} is not a valid number of seconds"))
+}
+
+// A client for the Advent of Code website (or anything serving the same
+// endpoints), which waits between requests so as not to put any strain on the
+// server. The time of the last request is only known to this client, so the
+// throttle does not apply across runs.
+pub(crate) struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+    throttle: Duration,
+    last_request: Option<Instant>,
+}
+
+impl Client {
+    pub fn new(args: &ClientArgs) -> Result<Self, Box<dyn Error>> {
+        let session = match env::var(SESSION_VAR) {
+            Ok(session) => session,
+            Err(_) => fs::read_to_string(&args.session_file).map_err(|err| {
+                format!(
+                    "cannot read the session cookie from {:?} ({err}), set {SESSION_VAR} or use --session-file",
+                    args.session_file
+                )
+            })?,
+        };
+        Ok(Self {
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+            base_url: args.base_url.trim_end_matches('/').to_owned(),
+            session: session.trim().to_owned(),
+            throttle: args.throttle,
+            last_request: None,
+        })
+    }
+
+    fn wait(&mut self) {
+        if let Some(last_request) = self.last_request {
+            thread::sleep(self.throttle.saturating_sub(last_request.elapsed()));
+        }
+        self.last_request = Some(Instant::now());
+    }
+
+    fn request(&mut self, method: &str, path: &str) -> ureq::Request {
+        self.wait();
+        self.agent
+            .request(method, &format!("{}{path}", self.base_url))
+            .set("Cookie", &format!("session={}", self.session))
+    }
+
+    pub fn input(&mut self, year: usize, day: usize) -> Result<String, Box<dyn Error>> {
+        body(self.request("GET", &format!("/{year}/day/{day}/input")).call())
+    }
+
+    // Submits an answer and returns the page with the response.
+    pub fn submit(&mut self, year: usize, day: usize, part: usize, answer: &str) -> Result<String, Box<dyn Error>> {
+        let level = part.to_string();
+        body(
+            self.request("POST", &format!("/{year}/day/{day}/answer"))
+                .send_form(&[("level", &level), ("answer", answer)]),
+        )
+    }
+}
+
+fn body(result: Result<ureq::Response, ureq::Error>) -> Result<String, Box<dyn Error>> {
+    match result {
+        Ok(response) => Ok(response.into_string()?),
+        Err(ureq::Error::Status(code, response)) => {
+            let body = response.into_string().unwrap_or_default();
+            Err(format!("request failed with status {code}: {}", body.trim()).into())
+        }
+        Err(err) => Err(err.into()),
+    }
+}
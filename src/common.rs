@@ -1,4 +1,409 @@
-use std::{error::Error, fmt::Display, fs::File};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fmt::{self, Display},
+    io::BufRead,
+    ops::Range,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
 
-pub(crate) type Solution = (Box<dyn Display>, Box<dyn Display>);
-pub(crate) type SolverFn = fn(File) -> Result<Solution, Box<dyn Error>>;
+/// The answer to a part of a puzzle. Non-negative integers are always stored as
+/// `Unsigned`, so that answers compare equal regardless of the integer type the
+/// solver used, and so that they survive a round-trip through a string or JSON.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Answer {
+    Unsigned(u64),
+    Signed(i64),
+    Text(String),
+    Image(Vec<String>),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Unsigned(n) => write!(f, "{n}"),
+            Answer::Signed(n) => write!(f, "{n}"),
+            Answer::Text(s) => write!(f, "{s}"),
+            Answer::Image(lines) => write!(f, "{}", lines.join("\n")),
+        }
+    }
+}
+
+impl FromStr for Answer {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(Box::from("empty answer"));
+        }
+        Ok(if s.contains('\n') {
+            Answer::Image(s.lines().map(|line| line.trim().to_owned()).collect())
+        } else if let Ok(n) = s.parse::<u64>() {
+            Answer::Unsigned(n)
+        } else if let Ok(n) = s.parse::<i64>() {
+            Answer::Signed(n)
+        } else {
+            Answer::Text(s.to_owned())
+        })
+    }
+}
+
+macro_rules! impl_from_unsigned {
+    ($($t:ty),*) => {$(
+        impl From<$t> for Answer {
+            fn from(n: $t) -> Self {
+                Answer::Unsigned(n as u64)
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_from_signed {
+    ($($t:ty),*) => {$(
+        impl From<$t> for Answer {
+            fn from(n: $t) -> Self {
+                if n >= 0 {
+                    Answer::Unsigned(n as u64)
+                } else {
+                    Answer::Signed(n as i64)
+                }
+            }
+        }
+    )*};
+}
+
+impl_from_unsigned!(u8, u16, u32, u64, usize);
+impl_from_signed!(i8, i16, i32, i64, isize);
+
+// Text that is an integer is stored as one, like when parsing an answer, so that
+// it compares equal to the expected answers.
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        if let Ok(n) = s.parse::<u64>() {
+            Answer::Unsigned(n)
+        } else if let Ok(n) = s.parse::<i64>() {
+            Answer::Signed(n)
+        } else {
+            Answer::Text(s)
+        }
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::from(s.to_owned())
+    }
+}
+
+impl From<Vec<String>> for Answer {
+    fn from(lines: Vec<String>) -> Self {
+        Answer::Image(lines)
+    }
+}
+
+/// The answers to both parts of a puzzle, `None` for a part that was not solved.
+pub type Solution = (Option<Answer>, Option<Answer>);
+
+/// An error in the puzzle input, pointing at the offending span of a line. The
+/// day is filled in by `solve_reader`, as the solvers don't know their day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: Option<usize>,
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub span: Range<usize>,
+    pub hint: String,
+}
+
+impl ParseError {
+    /// Points at the whole of the line with the 0-based index `i`.
+    pub fn line(i: usize, text: &str, hint: impl Into<String>) -> Self {
+        Self::span(i, text, 0..text.len(), hint)
+    }
+
+    /// Points at `part`, which should be a slice of `text` (otherwise its first
+    /// occurrence in `text` is used).
+    pub fn at(i: usize, text: &str, part: &str, hint: impl Into<String>) -> Self {
+        let start = (part.as_ptr() as usize)
+            .checked_sub(text.as_ptr() as usize)
+            .filter(|start| start + part.len() <= text.len())
+            .or_else(|| text.find(part))
+            .unwrap_or(0);
+        Self::span(i, text, start..start + part.len(), hint)
+    }
+
+    /// Points at the given byte range of `text`.
+    pub fn span(i: usize, text: &str, span: Range<usize>, hint: impl Into<String>) -> Self {
+        Self {
+            day: None,
+            line: i + 1,
+            column: text[..span.start].chars().count() + 1,
+            text: text.to_owned(),
+            span,
+            hint: hint.into(),
+        }
+    }
+
+    /// The offending line with a caret under the span, prefixed by the line
+    /// number.
+    pub fn snippet(&self) -> String {
+        let number = self.line.to_string();
+        let width = self.text[self.span.clone()].chars().count().max(1);
+        format!(
+            "{:pad$} |\n{number} | {}\n{:pad$} | {:indent$}{}",
+            "",
+            self.text,
+            "",
+            "",
+            "^".repeat(width),
+            pad = number.len(),
+            indent = self.column - 1,
+        )
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "malformed input")?;
+        if let Some(day) = self.day {
+            write!(f, " for day {day}")?;
+        }
+        write!(f, " at line {}, column {}: {}", self.line, self.column, self.hint)
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses `field`, a slice of the line `text` with the 0-based index `i`,
+/// pointing at it if it is invalid.
+pub fn parse_field<T>(i: usize, text: &str, field: &str) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    field
+        .parse()
+        .map_err(|err| ParseError::at(i, text, field, format!("{err}")))
+}
+
+/// A solver for a day, as registered in `SOLVERS`, which reads the input, solves
+/// the given part (or both) and returns the answers with their timings.
+pub type SolverFn =
+    fn(&mut dyn BufRead, Option<usize>, &Params, &CancellationToken) -> Result<(Solution, Timings), Box<dyn Error>>;
+
+/// A parameter of a solver, for a constant of the puzzle that is different for
+/// the example, e.g. the number of rounds to play. The default is the value for
+/// the puzzle input.
+#[derive(Clone, Copy, Debug)]
+pub struct Param {
+    pub name: &'static str,
+    pub default: &'static str,
+    pub description: &'static str,
+}
+
+impl Param {
+    /// The default parsed into the type of the parameter. Panics if it is invalid,
+    /// as the defaults are part of the solver.
+    pub fn default_value<T: FromStr>(&self) -> T {
+        match self.default.parse() {
+            Ok(value) => value,
+            Err(_) => panic!("invalid default value for parameter {}", self.name),
+        }
+    }
+}
+
+/// The values given for the parameters of a solver, by name. Values are kept as
+/// text, and are parsed into the type of the parameter when read.
+#[derive(Clone, Debug, Default)]
+pub struct Params(BTreeMap<String, String>);
+
+impl Params {
+    /// Sets the value of the parameter called `name`.
+    pub fn set(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        self.0.insert(name.into(), value.into());
+    }
+
+    /// The values that were given, by name in alphabetical order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0.iter().map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    /// The value of `param`, or its default if it was not given.
+    pub fn get<T>(&self, param: &Param) -> Result<T, Box<dyn Error>>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        match self.0.get(param.name) {
+            Some(value) => value
+                .parse()
+                .map_err(|err| format!("invalid value {value:?} for parameter {}: {err}", param.name).into()),
+            None => Ok(param.default_value()),
+        }
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> FromIterator<(K, V)> for Params {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self(
+            iter.into_iter()
+                .map(|(name, value)| (name.into(), value.into()))
+                .collect(),
+        )
+    }
+}
+
+/// A token that long-running solvers check in their main loops, so that they stop
+/// cleanly once the run is cancelled (e.g. because it timed out).
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Asks the solvers checking this token to stop.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether `cancel` was called on this token or any of its clones.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Returns an error once the token is cancelled, to stop a solver with `?`.
+    pub fn check(&self) -> Result<(), Box<dyn Error>> {
+        if self.is_cancelled() {
+            return Err(Box::from("cancelled"));
+        }
+        Ok(())
+    }
+}
+
+/// The solution of a day, which parses the input once for both parts.
+pub trait Solver {
+    type Input;
+
+    /// The parameters of the solver, which are all read in `set_params`.
+    const PARAMS: &'static [Param] = &[];
+
+    /// Gives the solver the token to check for cancellation. Solvers without
+    /// long-running loops can ignore it.
+    fn set_cancellation(&mut self, _cancel: CancellationToken) {}
+
+    /// Gives the solver the values of its parameters, which are only ever the
+    /// ones in `PARAMS`. Solvers without parameters can ignore them.
+    fn set_params(&mut self, _params: &Params) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+
+    /// Reads the puzzle input.
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Self::Input, Box<dyn Error>>;
+    /// Solves the first part of the puzzle.
+    fn part_1(&self, input: &Self::Input) -> Result<Answer, Box<dyn Error>>;
+    /// Solves the second part of the puzzle.
+    fn part_2(&self, input: &Self::Input) -> Result<Answer, Box<dyn Error>>;
+}
+
+/// How long parsing the input and solving each part took.
+#[derive(Clone, Copy, Default)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part_1: Option<Duration>,
+    pub part_2: Option<Duration>,
+}
+
+impl Timings {
+    /// The time of the whole run.
+    pub fn total(&self) -> Duration {
+        self.parse + self.part_1.unwrap_or_default() + self.part_2.unwrap_or_default()
+    }
+}
+
+/// Adapts any solver to a `SolverFn`, solving only the given part, or both
+/// parts if `part` is `None`.
+pub fn solve<S: Solver + Default>(
+    input: &mut dyn BufRead,
+    part: Option<usize>,
+    params: &Params,
+    cancel: &CancellationToken,
+) -> Result<(Solution, Timings), Box<dyn Error>> {
+    let mut solver = S::default();
+    if let Some((name, _)) = params
+        .iter()
+        .find(|(name, _)| S::PARAMS.iter().all(|param| param.name != *name))
+    {
+        return Err(format!("unknown parameter {name}").into());
+    }
+    solver.set_params(params)?;
+    solver.set_cancellation(cancel.clone());
+    let start = Instant::now();
+    let parsed = solver.parse(input)?;
+    let parse_time = start.elapsed();
+    let (mut result_1, mut part_1_time) = (None, None);
+    if part != Some(2) {
+        let start = Instant::now();
+        result_1 = Some(solver.part_1(&parsed)?);
+        part_1_time = Some(start.elapsed());
+    }
+    let (mut result_2, mut part_2_time) = (None, None);
+    if part != Some(1) {
+        let start = Instant::now();
+        result_2 = Some(solver.part_2(&parsed)?);
+        part_2_time = Some(start.elapsed());
+    }
+    Ok((
+        (result_1, result_2),
+        Timings {
+            parse: parse_time,
+            part_1: part_1_time,
+            part_2: part_2_time,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_answers() {
+        assert_eq!("5".parse::<Answer>().unwrap(), Answer::Unsigned(5));
+        assert_eq!("-5".parse::<Answer>().unwrap(), Answer::Signed(-5));
+        assert_eq!("abc".parse::<Answer>().unwrap(), Answer::Text("abc".to_owned()));
+        assert_eq!(
+            "#..#\n.##.\n".parse::<Answer>().unwrap(),
+            Answer::Image(vec!["#..#".to_owned(), ".##.".to_owned()])
+        );
+        assert!(" ".parse::<Answer>().is_err());
+    }
+
+    #[test]
+    fn stores_integers_regardless_of_type() {
+        assert_eq!(Answer::from(5i32), Answer::from(5u64));
+        assert_eq!(Answer::from(-5i8), Answer::Signed(-5));
+        assert_eq!(Answer::from("123"), "123".parse::<Answer>().unwrap());
+        assert_eq!(Answer::from("-5".to_owned()), Answer::Signed(-5));
+        assert_eq!(Answer::from("CMZ"), Answer::Text("CMZ".to_owned()));
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        for answer in [
+            Answer::Unsigned(5),
+            Answer::Signed(-5),
+            Answer::Text("123".to_owned()),
+            Answer::Image(vec!["#..#".to_owned(), ".##.".to_owned()]),
+        ] {
+            let json = serde_json::to_string(&answer).unwrap();
+            assert_eq!(serde_json::from_str::<Answer>(&json).unwrap(), answer);
+        }
+    }
+}
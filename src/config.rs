@@ -0,0 +1,104 @@
+use serde::Deserialize;
+use std::{
+    collections::BTreeMap,
+    env,
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+};
+
+const FILE_NAME: &str = "aoc.toml";
+
+// The defaults of the arguments for a machine or checkout, read from the first
+// `aoc.toml` in the current directory or any of its parents. Arguments given on
+// the command line take precedence over the config.
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct Config {
+    input: Option<PathBuf>,
+    layout: Option<String>,
+    format: Option<String>,
+    timeout: Option<f64>,
+    jobs: Option<usize>,
+    cache: Option<bool>,
+    // The parameters of each day, in a table per year and day, e.g.
+    // `[params.2022.15]`.
+    params: BTreeMap<String, BTreeMap<String, BTreeMap<String, toml::Value>>>,
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+impl Config {
+    // Finds and reads the config, or returns an empty config if there is none.
+    pub fn find() -> Result<Self, Box<dyn Error>> {
+        let cwd = env::current_dir()?;
+        let Some(path) = cwd
+            .ancestors()
+            .map(|dir| dir.join(FILE_NAME))
+            .find(|path| path.is_file())
+        else {
+            return Ok(Self::default());
+        };
+        let mut config = toml::from_str::<Self>(&fs::read_to_string(&path)?)
+            .map_err(|err| format!("malformed config file {}: {err}", path.display()))?;
+        for (year, days) in &config.params {
+            if year.parse::<usize>().is_err() {
+                return Err(format!("malformed config file {}: {year:?} is not a year", path.display()).into());
+            }
+            if let Some(day) = days.keys().find(|day| day.parse::<usize>().is_err()) {
+                return Err(format!("malformed config file {}: {day:?} is not a day", path.display()).into());
+            }
+        }
+        config.path = path;
+        Ok(config)
+    }
+
+    // Sets the values in the config as the defaults of the arguments.
+    pub fn apply(&self, mut command: clap::Command) -> clap::Command {
+        // The input directory is relative to the config, as the config is also
+        // used from any directory below it.
+        let dir = self.path.parent().unwrap_or(Path::new(""));
+        let defaults = [
+            (
+                "input",
+                self.input.as_ref().map(|input| match input.to_str() {
+                    Some("-") => "-".to_owned(),
+                    _ => dir.join(input).to_string_lossy().into_owned(),
+                }),
+            ),
+            ("layout", self.layout.clone()),
+            ("format", self.format.clone()),
+            ("timeout", self.timeout.map(|timeout| timeout.to_string())),
+            ("jobs", self.jobs.map(|jobs| jobs.to_string())),
+            ("cache", self.cache.map(|cache| cache.to_string())),
+        ];
+        for (name, value) in defaults {
+            if let Some(value) = value {
+                command = command.mut_arg(name, |arg| arg.default_value(value));
+            }
+        }
+        command
+    }
+
+    // The parameters set for a day of a year, by name.
+    pub fn params(&self, year: usize, day: usize) -> Vec<(String, String)> {
+        self.params
+            .iter()
+            .filter(|(y, _)| y.parse() == Ok(year))
+            .flat_map(|(_, days)| days.iter())
+            .filter(|(d, _)| d.parse() == Ok(day))
+            .flat_map(|(_, params)| params.iter())
+            .map(|(name, value)| {
+                let value = match value {
+                    toml::Value::String(value) => value.clone(),
+                    value => value.to_string(),
+                };
+                (name.clone(), value)
+            })
+            .collect()
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
@@ -1,9 +1,5 @@
-use crate::*;
-use std::{
-    error::Error,
-    fs::File,
-    io::{BufRead, BufReader},
-};
+use crate::common::*;
+use std::{error::Error, io::BufRead};
 
 #[inline]
 fn update_totals(cur_total: u32, top_three_totals: &mut [u32; 3]) {
@@ -18,22 +14,43 @@ fn update_totals(cur_total: u32, top_three_totals: &mut [u32; 3]) {
     }
 }
 
-pub fn solve(input: File) -> Result<Solution, Box<dyn Error>> {
-    let reader = BufReader::new(input);
+pub struct Inventory(pub Vec<u32>); // Total calories carried by each elf.
+
+fn top_three_totals(inventory: &Inventory) -> [u32; 3] {
     let mut top_three_totals = [0u32; 3];
-    let mut cur_total: u32 = 0;
-    for line in reader.lines() {
-        let calories = line?;
-        if calories.is_empty() {
-            update_totals(cur_total, &mut top_three_totals);
-            cur_total = 0;
-        } else {
-            cur_total += calories.parse::<u32>()?;
+    for &total in inventory.0.iter() {
+        update_totals(total, &mut top_three_totals);
+    }
+    top_three_totals
+}
+
+#[derive(Default)]
+pub struct Day01;
+
+impl Solver for Day01 {
+    type Input = Inventory;
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Inventory, Box<dyn Error>> {
+        let mut totals = Vec::new();
+        let mut cur_total: u32 = 0;
+        for (i, line) in input.lines().enumerate() {
+            let calories = line?;
+            if calories.is_empty() {
+                totals.push(cur_total);
+                cur_total = 0;
+            } else {
+                cur_total += parse_field::<u32>(i, &calories, &calories)?;
+            }
         }
+        totals.push(cur_total);
+        Ok(Inventory(totals))
+    }
+
+    fn part_1(&self, inventory: &Inventory) -> Result<Answer, Box<dyn Error>> {
+        Ok(top_three_totals(inventory)[0].into())
+    }
+
+    fn part_2(&self, inventory: &Inventory) -> Result<Answer, Box<dyn Error>> {
+        Ok(top_three_totals(inventory).iter().sum::<u32>().into())
     }
-    update_totals(cur_total, &mut top_three_totals);
-    Ok((
-        Box::new(top_three_totals[0]),
-        Box::new(top_three_totals.iter().sum::<u32>()),
-    ))
 }
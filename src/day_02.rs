@@ -1,9 +1,5 @@
-use crate::*;
-use std::{
-    error::Error,
-    fs::File,
-    io::{BufRead, BufReader},
-};
+use crate::common::*;
+use std::{error::Error, io::BufRead};
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum GameResult {
@@ -23,7 +19,7 @@ impl From<bool> for GameResult {
 }
 
 #[derive(Clone, Copy, PartialEq, Eq)]
-enum RockPaperScissors {
+pub enum RockPaperScissors {
     Rock = 1,
     Paper = 2,
     Scissors = 3,
@@ -77,29 +73,68 @@ fn round_score(p_1: RockPaperScissors, p_2: RockPaperScissors) -> u32 {
     score
 }
 
-pub fn solve(input: File) -> Result<Solution, Box<dyn Error>> {
-    let reader = BufReader::new(input);
-    let mut total_score_a: u32 = 0;
-    let mut total_score_b: u32 = 0;
-    for (i, line) in reader.lines().enumerate() {
-        let l = line?;
-        let (lhs, rhs) = l
-            .split_once(' ')
-            .ok_or_else(|| format!("malformed input at line {}", i + 1))?;
-        let p_1 = match lhs {
-            "A" => Ok(RockPaperScissors::Rock),
-            "B" => Ok(RockPaperScissors::Paper),
-            "C" => Ok(RockPaperScissors::Scissors),
-            _ => Err(format!("malformed input at line {}", i + 1)),
-        }?;
-        let (p_2_a, p_2_b) = match rhs {
-            "X" => Ok((RockPaperScissors::Rock, p_1.want_result(GameResult::Loss))),
-            "Y" => Ok((RockPaperScissors::Paper, p_1.want_result(GameResult::Draw))),
-            "Z" => Ok((RockPaperScissors::Scissors, p_1.want_result(GameResult::Win))),
-            _ => Err(format!("malformed input at line {}", i + 1)),
-        }?;
-        total_score_a += round_score(p_1, p_2_a);
-        total_score_b += round_score(p_1, p_2_b);
+#[derive(Clone, Copy)]
+pub enum Response {
+    X,
+    Y,
+    Z,
+}
+
+pub struct StrategyGuide(pub Vec<(RockPaperScissors, Response)>);
+
+#[derive(Default)]
+pub struct Day02;
+
+impl Solver for Day02 {
+    type Input = StrategyGuide;
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<StrategyGuide, Box<dyn Error>> {
+        let mut rounds = Vec::new();
+        for (i, line) in input.lines().enumerate() {
+            let l = line?;
+            let (lhs, rhs) = l
+                .split_once(' ')
+                .ok_or_else(|| ParseError::line(i, &l, "expected two columns separated by a space"))?;
+            let p_1 = match lhs {
+                "A" => Ok(RockPaperScissors::Rock),
+                "B" => Ok(RockPaperScissors::Paper),
+                "C" => Ok(RockPaperScissors::Scissors),
+                _ => Err(ParseError::at(i, &l, lhs, "expected one of A, B or C")),
+            }?;
+            let response = match rhs {
+                "X" => Ok(Response::X),
+                "Y" => Ok(Response::Y),
+                "Z" => Ok(Response::Z),
+                _ => Err(ParseError::at(i, &l, rhs, "expected one of X, Y or Z")),
+            }?;
+            rounds.push((p_1, response));
+        }
+        Ok(StrategyGuide(rounds))
+    }
+
+    fn part_1(&self, guide: &StrategyGuide) -> Result<Answer, Box<dyn Error>> {
+        let mut total_score: u32 = 0;
+        for &(p_1, response) in guide.0.iter() {
+            let p_2 = match response {
+                Response::X => RockPaperScissors::Rock,
+                Response::Y => RockPaperScissors::Paper,
+                Response::Z => RockPaperScissors::Scissors,
+            };
+            total_score += round_score(p_1, p_2);
+        }
+        Ok(total_score.into())
+    }
+
+    fn part_2(&self, guide: &StrategyGuide) -> Result<Answer, Box<dyn Error>> {
+        let mut total_score: u32 = 0;
+        for &(p_1, response) in guide.0.iter() {
+            let p_2 = match response {
+                Response::X => p_1.want_result(GameResult::Loss),
+                Response::Y => p_1.want_result(GameResult::Draw),
+                Response::Z => p_1.want_result(GameResult::Win),
+            };
+            total_score += round_score(p_1, p_2);
+        }
+        Ok(total_score.into())
     }
-    Ok((Box::new(total_score_a), Box::new(total_score_b)))
 }
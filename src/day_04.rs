@@ -1,29 +1,48 @@
-use crate::*;
-use std::{
-    error::Error,
-    fs::File,
-    io::{BufRead, BufReader},
-};
+use crate::common::*;
+use std::{error::Error, io::BufRead};
 
-pub fn solve(input: File) -> Result<Solution, Box<dyn Error>> {
-    let reader = BufReader::new(input);
-    let mut num_redundant_pairs: u32 = 0;
-    let mut num_overlapping_pairs: u32 = 0;
-    for (i, line) in reader.lines().enumerate() {
-        let section_assignment = line?;
-        let parts = section_assignment
-            .split(&['-', ','])
-            .map(|s| s.parse::<u32>())
-            .collect::<Result<Vec<_>, _>>()?;
-        let (start_1, end_1, start_2, end_2) = (parts.len() == 4)
-            .then(|| (parts[0], parts[1], parts[2], parts[3]))
-            .ok_or_else(|| format!("malformed input at line {}", i + 1))?;
-        if (start_1 <= start_2 && end_1 >= end_2) || (start_2 <= start_1 && end_2 >= end_1) {
-            num_redundant_pairs += 1;
+pub struct Assignments(pub Vec<(u32, u32, u32, u32)>);
+
+#[derive(Default)]
+pub struct Day04;
+
+impl Solver for Day04 {
+    type Input = Assignments;
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Assignments, Box<dyn Error>> {
+        let mut pairs = Vec::new();
+        for (i, line) in input.lines().enumerate() {
+            let section_assignment = line?;
+            let parts = section_assignment
+                .split(&['-', ','])
+                .map(|s| parse_field::<u32>(i, &section_assignment, s))
+                .collect::<Result<Vec<_>, _>>()?;
+            pairs.push(
+                (parts.len() == 4)
+                    .then(|| (parts[0], parts[1], parts[2], parts[3]))
+                    .ok_or_else(|| ParseError::line(i, &section_assignment, "expected two ranges like 2-4,6-8"))?,
+            );
+        }
+        Ok(Assignments(pairs))
+    }
+
+    fn part_1(&self, assignments: &Assignments) -> Result<Answer, Box<dyn Error>> {
+        let mut num_redundant_pairs: u32 = 0;
+        for &(start_1, end_1, start_2, end_2) in assignments.0.iter() {
+            if (start_1 <= start_2 && end_1 >= end_2) || (start_2 <= start_1 && end_2 >= end_1) {
+                num_redundant_pairs += 1;
+            }
         }
-        if (start_1 <= start_2 && end_1 >= start_2) || (start_2 <= start_1 && end_2 >= start_1) {
-            num_overlapping_pairs += 1;
+        Ok(num_redundant_pairs.into())
+    }
+
+    fn part_2(&self, assignments: &Assignments) -> Result<Answer, Box<dyn Error>> {
+        let mut num_overlapping_pairs: u32 = 0;
+        for &(start_1, end_1, start_2, end_2) in assignments.0.iter() {
+            if (start_1 <= start_2 && end_1 >= start_2) || (start_2 <= start_1 && end_2 >= start_1) {
+                num_overlapping_pairs += 1;
+            }
         }
+        Ok(num_overlapping_pairs.into())
     }
-    Ok((Box::from(num_redundant_pairs), Box::from(num_overlapping_pairs)))
 }
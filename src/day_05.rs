@@ -1,81 +1,102 @@
-use crate::*;
-use std::{
-    collections::VecDeque,
-    error::Error,
-    fs::File,
-    io::{BufRead, BufReader},
-};
+use crate::common::*;
+use std::{collections::VecDeque, error::Error, io::BufRead};
 
-pub fn solve(input: File) -> Result<Solution, Box<dyn Error>> {
-    let reader = BufReader::new(input);
-    let mut parse_arrangement = true;
-    let mut arrangement_a = Vec::new();
-    let mut arrangement_b = Vec::new();
-    for (i, line) in reader.lines().enumerate() {
-        let l = line?;
-        if parse_arrangement {
-            if l.is_empty() {
-                parse_arrangement = false;
-                arrangement_b = arrangement_a.clone();
-                continue;
-            }
-            if !l.contains('[') {
-                continue;
-            }
-            l.as_bytes()
-                .windows(3)
-                .enumerate()
-                .filter_map(|(j, b)| if j % 4 == 0 { Some(b[1] as char) } else { None })
-                .enumerate()
-                .for_each(|(idx, c)| {
-                    if c != ' ' {
-                        if idx >= arrangement_a.len() {
-                            arrangement_a.resize_with(idx + 1, VecDeque::new);
+pub struct Procedure {
+    pub arrangement: Vec<VecDeque<char>>,
+    pub moves: Vec<(usize, usize, usize)>,
+}
+
+fn top_crates(mut arrangement: Vec<VecDeque<char>>) -> Result<String, Box<dyn Error>> {
+    Ok(arrangement
+        .iter_mut()
+        .enumerate()
+        .map(|(i, stack)| {
+            stack
+                .pop_back()
+                .ok_or_else(|| format!("unable to remove entry from stack {}", i + 1))
+        })
+        .collect::<Result<String, _>>()?)
+}
+
+#[derive(Default)]
+pub struct Day05;
+
+impl Solver for Day05 {
+    type Input = Procedure;
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Procedure, Box<dyn Error>> {
+        let mut parse_arrangement = true;
+        let mut arrangement = Vec::new();
+        let mut moves = Vec::new();
+        for (i, line) in input.lines().enumerate() {
+            let l = line?;
+            if parse_arrangement {
+                if l.is_empty() {
+                    parse_arrangement = false;
+                    continue;
+                }
+                if !l.contains('[') {
+                    continue;
+                }
+                l.as_bytes()
+                    .windows(3)
+                    .enumerate()
+                    .filter_map(|(j, b)| if j % 4 == 0 { Some(b[1] as char) } else { None })
+                    .enumerate()
+                    .for_each(|(idx, c)| {
+                        if c != ' ' {
+                            if idx >= arrangement.len() {
+                                arrangement.resize_with(idx + 1, VecDeque::new);
+                            }
+                            arrangement[idx].push_front(c);
                         }
-                        arrangement_a[idx].push_front(c);
-                    }
-                });
-        } else {
-            let parts = l
-                .split(' ')
-                .enumerate()
-                .filter_map(|(j, n)| if j % 2 != 0 { Some(n.parse::<usize>()) } else { None })
-                .collect::<Result<Vec<_>, _>>()?;
-            if parts.len() != 3 {
-                return Err(format!("malformed input at line {}", i + 1).into());
+                    });
+            } else {
+                let parts = l
+                    .split(' ')
+                    .enumerate()
+                    .filter_map(|(j, n)| {
+                        if j % 2 != 0 {
+                            Some(parse_field::<usize>(i, &l, n))
+                        } else {
+                            None
+                        }
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                if parts.len() != 3 {
+                    return Err(ParseError::line(i, &l, "expected a move like `move 1 from 2 to 3`").into());
+                }
+                moves.push((parts[0], parts[1] - 1, parts[2] - 1));
             }
-            let (num, from, to) = (parts[0], parts[1] - 1, parts[2] - 1);
-            let mut block = Vec::with_capacity(num);
+        }
+        Ok(Procedure { arrangement, moves })
+    }
+
+    fn part_1(&self, procedure: &Procedure) -> Result<Answer, Box<dyn Error>> {
+        let mut arrangement = procedure.arrangement.clone();
+        for &(num, from, to) in procedure.moves.iter() {
             for _ in 0..num {
-                let c_a = arrangement_a[from]
+                let c = arrangement[from]
                     .pop_back()
                     .ok_or_else(|| format!("unable to remove entry from stack {}", from + 1))?;
-                arrangement_a[to].push_back(c_a);
-                let c_b = arrangement_b[from]
+                arrangement[to].push_back(c);
+            }
+        }
+        Ok(top_crates(arrangement)?.into())
+    }
+
+    fn part_2(&self, procedure: &Procedure) -> Result<Answer, Box<dyn Error>> {
+        let mut arrangement = procedure.arrangement.clone();
+        for &(num, from, to) in procedure.moves.iter() {
+            let mut block = Vec::with_capacity(num);
+            for _ in 0..num {
+                let c = arrangement[from]
                     .pop_back()
                     .ok_or_else(|| format!("unable to remove entry from stack {}", from + 1))?;
-                block.push(c_b);
+                block.push(c);
             }
-            block.into_iter().rev().for_each(|c| arrangement_b[to].push_back(c));
+            block.into_iter().rev().for_each(|c| arrangement[to].push_back(c));
         }
+        Ok(top_crates(arrangement)?.into())
     }
-    let top_crates_a = arrangement_a
-        .iter_mut()
-        .enumerate()
-        .map(|(i, stack)| {
-            stack
-                .pop_back()
-                .ok_or_else(|| format!("unable to remove entry from stack {}", i + 1))
-        })
-        .collect::<Result<String, _>>()?;
-    let top_crates_b = arrangement_b
-        .iter_mut()
-        .enumerate()
-        .map(|(i, stack)| {
-            stack
-                .pop_back()
-                .ok_or_else(|| format!("unable to remove entry from stack {}", i + 1))
-        })
-        .collect::<Result<String, _>>()?;
-    Ok((Box::new(top_crates_a), Box::new(top_crates_b)))
 }
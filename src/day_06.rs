@@ -1,5 +1,5 @@
-use crate::*;
-use std::{collections::HashSet, error::Error, fs::File, io::Read};
+use crate::common::*;
+use std::{collections::HashSet, error::Error, io::BufRead};
 
 #[inline]
 fn first_marker_occurence(data_stream: &[u8], n: usize) -> usize {
@@ -12,10 +12,25 @@ fn first_marker_occurence(data_stream: &[u8], n: usize) -> usize {
     0
 }
 
-pub fn solve(mut input: File) -> Result<Solution, Box<dyn Error>> {
-    let mut data_stream = Vec::new();
-    input.read_to_end(&mut data_stream)?;
-    let start_of_packet_marker = first_marker_occurence(&data_stream, 4);
-    let start_of_message_marker = first_marker_occurence(&data_stream, 14);
-    Ok((Box::new(start_of_packet_marker), Box::new(start_of_message_marker)))
+pub struct DataStream(pub Vec<u8>);
+
+#[derive(Default)]
+pub struct Day06;
+
+impl Solver for Day06 {
+    type Input = DataStream;
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<DataStream, Box<dyn Error>> {
+        let mut data_stream = Vec::new();
+        input.read_to_end(&mut data_stream)?;
+        Ok(DataStream(data_stream))
+    }
+
+    fn part_1(&self, data_stream: &DataStream) -> Result<Answer, Box<dyn Error>> {
+        Ok(first_marker_occurence(&data_stream.0, 4).into())
+    }
+
+    fn part_2(&self, data_stream: &DataStream) -> Result<Answer, Box<dyn Error>> {
+        Ok(first_marker_occurence(&data_stream.0, 14).into())
+    }
 }
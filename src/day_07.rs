@@ -1,9 +1,8 @@
-use crate::*;
+use crate::common::*;
 use std::{
     error::Error,
     fmt,
-    fs::File,
-    io::{BufRead, BufReader},
+    io::BufRead,
     rc::{Rc, Weak},
     slice::Iter,
 };
@@ -13,7 +12,7 @@ enum DirEntry {
     File((usize, String)),
 }
 
-struct Directory {
+pub struct Directory {
     ptr: Weak<Directory>,
     parent: Weak<Directory>,
     name: String,
@@ -30,7 +29,7 @@ impl Directory {
         })
     }
 
-    fn size(&self) -> usize {
+    pub fn size(&self) -> usize {
         self.contents
             .iter()
             .map(|entry| match entry {
@@ -129,47 +128,105 @@ fn min_dir_size_to_del(base: Rc<Directory>, treshold: usize) -> usize {
     min_size
 }
 
-pub fn solve(input: File) -> Result<Solution, Box<dyn Error>> {
-    let reader = BufReader::new(input);
-    let root = Directory::new("/");
-    let mut current_dir = root.clone();
-    for (i, line) in reader.lines().enumerate() {
-        let term = line?;
-        let parts = term.split(' ').collect::<Vec<_>>();
-        match parts[0] {
-            "$" => match parts[1] {
-                "cd" => match parts[2] {
-                    "/" => current_dir = root.clone(),
-                    ".." => {
-                        current_dir = current_dir
-                            .parent
-                            .upgrade()
-                            .ok_or_else(|| format!("directory {} does not have a parent", current_dir.name))?
-                    }
-                    dir_name => {
-                        current_dir = current_dir.get_dir(dir_name).ok_or_else(|| {
+const THRESHOLD: Param = Param {
+    name: "threshold",
+    default: "100000",
+    description: "The maximum size of the directories to sum up",
+};
+const DISK_SIZE: Param = Param {
+    name: "disk_size",
+    default: "70000000",
+    description: "The total space on the disk",
+};
+const SPACE_NEEDED: Param = Param {
+    name: "space_needed",
+    default: "30000000",
+    description: "The free space needed on the disk for the update",
+};
+
+pub struct Day07 {
+    threshold: usize,
+    disk_size: usize,
+    space_needed: usize,
+}
+
+impl Default for Day07 {
+    fn default() -> Self {
+        Self {
+            threshold: THRESHOLD.default_value(),
+            disk_size: DISK_SIZE.default_value(),
+            space_needed: SPACE_NEEDED.default_value(),
+        }
+    }
+}
+
+impl Solver for Day07 {
+    type Input = Rc<Directory>;
+
+    const PARAMS: &'static [Param] = &[THRESHOLD, DISK_SIZE, SPACE_NEEDED];
+
+    fn set_params(&mut self, params: &Params) -> Result<(), Box<dyn Error>> {
+        self.threshold = params.get(&THRESHOLD)?;
+        self.disk_size = params.get(&DISK_SIZE)?;
+        self.space_needed = params.get(&SPACE_NEEDED)?;
+        Ok(())
+    }
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Rc<Directory>, Box<dyn Error>> {
+        let root = Directory::new("/");
+        let mut current_dir = root.clone();
+        for (i, line) in input.lines().enumerate() {
+            let term = line?;
+            let parts = term.split(' ').collect::<Vec<_>>();
+            match parts[..] {
+                ["$", "cd", "/"] => current_dir = root.clone(),
+                ["$", "cd", ".."] => {
+                    current_dir = current_dir.parent.upgrade().ok_or_else(|| {
+                        ParseError::at(
+                            i,
+                            &term,
+                            parts[2],
+                            format!("directory {} does not have a parent", current_dir.name),
+                        )
+                    })?
+                }
+                ["$", "cd", dir_name] => {
+                    current_dir = current_dir.get_dir(dir_name).ok_or_else(|| {
+                        ParseError::at(
+                            i,
+                            &term,
+                            dir_name,
                             format!(
                                 "directory {} does not have a directory called {}",
                                 current_dir.name, dir_name
-                            )
-                        })?
-                    }
+                            ),
+                        )
+                    })?
+                }
+                ["$", "ls"] => {}
+                ["$", ..] => return Err(ParseError::line(i, &term, "expected a `cd <dir>` or `ls` command").into()),
+                ["dir", name] => unsafe {
+                    Rc::get_mut_unchecked(&mut current_dir).push_dir(name);
+                },
+                [size, name] => unsafe {
+                    Rc::get_mut_unchecked(&mut current_dir).push_file(name, parse_field::<usize>(i, &term, size)?);
                 },
-                "ls" => {}
-                _ => return Err(format!("malformed input at line {}", i + 1).into()),
-            },
-            "dir" => unsafe {
-                Rc::get_mut_unchecked(&mut current_dir).push_dir(parts[1]);
-            },
-            size => unsafe {
-                Rc::get_mut_unchecked(&mut current_dir).push_file(parts[1], size.parse::<usize>()?);
-            },
+                _ => return Err(ParseError::line(i, &term, "expected a command, a directory or a file").into()),
+            }
         }
+        Ok(root)
+    }
+
+    fn part_1(&self, root: &Rc<Directory>) -> Result<Answer, Box<dyn Error>> {
+        Ok(small_dir_sum(root.clone(), self.threshold).into())
+    }
+
+    fn part_2(&self, root: &Rc<Directory>) -> Result<Answer, Box<dyn Error>> {
+        let free_space = self
+            .disk_size
+            .checked_sub(root.size())
+            .ok_or("the files take up more space than the disk has")?;
+        let space_needed = self.space_needed.saturating_sub(free_space);
+        Ok(min_dir_size_to_del(root.clone(), space_needed).into())
     }
-    let free_space = 70000000 - root.size();
-    let space_needed = 30000000 - free_space;
-    Ok((
-        Box::new(small_dir_sum(root.clone(), 100000)),
-        Box::new(min_dir_size_to_del(root, space_needed)),
-    ))
 }
@@ -1,9 +1,5 @@
-use crate::*;
-use std::{
-    error::Error,
-    fs::File,
-    io::{BufRead, BufReader},
-};
+use crate::common::*;
+use std::{error::Error, io::BufRead};
 
 fn check_view<'a, I: Iterator<Item = &'a u8>>(height: u8, trees: I) -> (bool, usize) {
     let mut visible = true;
@@ -18,7 +14,7 @@ fn check_view<'a, I: Iterator<Item = &'a u8>>(height: u8, trees: I) -> (bool, us
     (visible, distance)
 }
 
-struct TreeGrid(Vec<Vec<u8>>);
+pub struct TreeGrid(pub Vec<Vec<u8>>);
 
 impl TreeGrid {
     fn new() -> Self {
@@ -51,32 +47,48 @@ impl TreeGrid {
     }
 }
 
-pub fn solve(input: File) -> Result<Solution, Box<dyn Error>> {
-    let reader = BufReader::new(input);
-    let mut grid = TreeGrid::new();
-    for (i, line) in reader.lines().enumerate() {
-        let row = line?;
-        grid.0.push(
-            row.chars()
-                .into_iter()
-                .map(|c| {
-                    c.to_digit(10)
-                        .map(|d| d as u8)
-                        .ok_or_else(|| format!("malformed input at line {}", i + 1))
-                })
-                .collect::<Result<_, _>>()?,
-        );
+#[derive(Default)]
+pub struct Day08;
+
+impl Solver for Day08 {
+    type Input = TreeGrid;
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<TreeGrid, Box<dyn Error>> {
+        let mut grid = TreeGrid::new();
+        for (i, line) in input.lines().enumerate() {
+            let row = line?;
+            grid.0.push(
+                row.char_indices()
+                    .map(|(j, c)| {
+                        c.to_digit(10)
+                            .map(|d| d as u8)
+                            .ok_or_else(|| ParseError::span(i, &row, j..j + c.len_utf8(), "expected a digit"))
+                    })
+                    .collect::<Result<_, _>>()?,
+            );
+        }
+        Ok(grid)
+    }
+
+    fn part_1(&self, grid: &TreeGrid) -> Result<Answer, Box<dyn Error>> {
+        let mut num_visible = 0;
+        for x in 0..grid.0.len() {
+            for y in 0..grid.0[0].len() {
+                if grid.scenic_score(x, y).0 {
+                    num_visible += 1;
+                }
+            }
+        }
+        Ok(num_visible.into())
     }
-    let mut num_visible = 0;
-    let mut max_scenic_score = 0;
-    for x in 0..grid.0.len() {
-        for y in 0..grid.0[0].len() {
-            let (is_visible, scenic_score) = grid.scenic_score(x, y);
-            if is_visible {
-                num_visible += 1;
+
+    fn part_2(&self, grid: &TreeGrid) -> Result<Answer, Box<dyn Error>> {
+        let mut max_scenic_score = 0;
+        for x in 0..grid.0.len() {
+            for y in 0..grid.0[0].len() {
+                max_scenic_score = max_scenic_score.max(grid.scenic_score(x, y).1);
             }
-            max_scenic_score = max_scenic_score.max(scenic_score);
         }
+        Ok(max_scenic_score.into())
     }
-    Ok((Box::new(num_visible), Box::new(max_scenic_score)))
 }
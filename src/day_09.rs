@@ -1,13 +1,8 @@
-use crate::*;
-use std::{
-    collections::HashSet,
-    error::Error,
-    fs::File,
-    io::{BufRead, BufReader},
-};
+use crate::common::*;
+use std::{collections::HashSet, error::Error, io::BufRead};
 
 #[derive(Clone, Copy)]
-enum Direction {
+pub enum Direction {
     Up,
     Down,
     Left,
@@ -52,31 +47,82 @@ fn update_rope(dir: Direction, rope: &mut [Position], rope_set: &mut HashSet<Pos
     rope_set.insert(rope[rope.len() - 1]);
 }
 
-pub fn solve(input: File) -> Result<Solution, Box<dyn Error>> {
-    let reader = BufReader::new(input);
-    let mut short_rope = [Position(0, 0); 2];
-    let mut long_rope = [Position(0, 0); 10];
-    let mut short_rope_set = HashSet::new();
-    let mut long_rope_set = HashSet::new();
-    short_rope_set.insert(short_rope[short_rope.len() - 1]);
-    long_rope_set.insert(long_rope[long_rope.len() - 1]);
-    for (i, line) in reader.lines().enumerate() {
-        let motion = line?;
-        let parts = motion
-            .split_once(' ')
-            .ok_or_else(|| format!("malformed input at line {}", i + 1))?;
-        let dir = match parts.0 {
-            "U" => Direction::Up,
-            "D" => Direction::Down,
-            "L" => Direction::Left,
-            "R" => Direction::Right,
-            _ => return Err(format!("malformed input at line {}", i + 1).into()),
-        };
-        let steps = parts.1.parse::<usize>()?;
+pub struct Motions(pub Vec<(Direction, usize)>);
+
+fn tail_positions(motions: &Motions, rope: &mut [Position]) -> usize {
+    let mut rope_set = HashSet::new();
+    rope_set.insert(rope[rope.len() - 1]);
+    for &(dir, steps) in motions.0.iter() {
         for _ in 0..steps {
-            update_rope(dir, &mut short_rope, &mut short_rope_set);
-            update_rope(dir, &mut long_rope, &mut long_rope_set);
+            update_rope(dir, rope, &mut rope_set);
         }
     }
-    Ok((Box::new(short_rope_set.len()), Box::new(long_rope_set.len())))
+    rope_set.len()
+}
+
+const KNOTS_1: Param = Param {
+    name: "knots_1",
+    default: "2",
+    description: "The number of knots of the rope in part 1",
+};
+const KNOTS_2: Param = Param {
+    name: "knots_2",
+    default: "10",
+    description: "The number of knots of the rope in part 2",
+};
+
+pub struct Day09 {
+    knots_1: usize,
+    knots_2: usize,
+}
+
+impl Default for Day09 {
+    fn default() -> Self {
+        Self {
+            knots_1: KNOTS_1.default_value(),
+            knots_2: KNOTS_2.default_value(),
+        }
+    }
+}
+
+impl Solver for Day09 {
+    type Input = Motions;
+
+    const PARAMS: &'static [Param] = &[KNOTS_1, KNOTS_2];
+
+    fn set_params(&mut self, params: &Params) -> Result<(), Box<dyn Error>> {
+        self.knots_1 = params.get(&KNOTS_1)?;
+        self.knots_2 = params.get(&KNOTS_2)?;
+        if self.knots_1 == 0 || self.knots_2 == 0 {
+            return Err(Box::from("a rope needs at least one knot"));
+        }
+        Ok(())
+    }
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Motions, Box<dyn Error>> {
+        let mut motions = Vec::new();
+        for (i, line) in input.lines().enumerate() {
+            let motion = line?;
+            let parts = motion
+                .split_once(' ')
+                .ok_or_else(|| ParseError::line(i, &motion, "expected a direction and a number of steps"))?;
+            let dir = match parts.0 {
+                "U" => Direction::Up,
+                "D" => Direction::Down,
+                "L" => Direction::Left,
+                "R" => Direction::Right,
+                _ => return Err(ParseError::at(i, &motion, parts.0, "expected one of U, D, L or R").into()),
+            };
+            motions.push((dir, parse_field::<usize>(i, &motion, parts.1)?));
+        }
+        Ok(Motions(motions))
+    }
+
+    fn part_1(&self, motions: &Motions) -> Result<Answer, Box<dyn Error>> {
+        Ok(tail_positions(motions, &mut vec![Position(0, 0); self.knots_1]).into())
+    }
+
+    fn part_2(&self, motions: &Motions) -> Result<Answer, Box<dyn Error>> {
+        Ok(tail_positions(motions, &mut vec![Position(0, 0); self.knots_2]).into())
+    }
 }
@@ -1,55 +1,72 @@
-use crate::*;
-use std::{
-    error::Error,
-    fmt::Write,
-    fs::File,
-    io::{BufRead, BufReader},
-};
-
-#[inline]
-fn update(signal_strength: &mut i32, crt_screen: &mut [[bool; 40]; 6], x: i32, cycle: usize) {
-    if cycle > 240 {
-        return;
-    }
-    match cycle {
-        20 | 60 | 100 | 140 | 180 | 220 => *signal_strength += x * (cycle as i32),
-        _ => (),
+use crate::common::*;
+use std::{error::Error, io::BufRead};
+
+#[derive(Clone, Copy)]
+pub enum Instruction {
+    Noop,
+    Addx(i32),
+}
+
+pub struct Program(pub Vec<Instruction>);
+
+impl Program {
+    // Returns the value of the X register during each cycle, starting from cycle 1.
+    fn register_values(&self) -> Vec<i32> {
+        let mut x = 1;
+        let mut values = Vec::with_capacity(self.0.len() * 2 + 1);
+        for instruction in self.0.iter() {
+            values.push(x);
+            if let Instruction::Addx(v) = instruction {
+                values.push(x);
+                x += v;
+            }
+        }
+        values.push(x);
+        values
     }
-    let row = (cycle - 1) / 40;
-    let pos = cycle - (40 * row) - 1;
-    crt_screen[row][pos] = (x - (pos as i32)).abs() <= 1;
 }
 
-pub fn solve(input: File) -> Result<Solution, Box<dyn Error>> {
-    let reader = BufReader::new(input);
-    let mut cycle = 1;
-    let mut x = 1;
-    let mut signal_strength = 0;
-    let mut crt_screen = [[false; 40]; 6];
-    for (i, line) in reader.lines().enumerate() {
-        let command = line?;
-        let (instruction, v) = command.split_once(' ').unwrap_or((&command, ""));
-        update(&mut signal_strength, &mut crt_screen, x, cycle);
-        match instruction {
-            "addx" => {
-                cycle += 1;
-                update(&mut signal_strength, &mut crt_screen, x, cycle);
-                x += v.parse::<i32>()?;
-                cycle += 1;
+#[derive(Default)]
+pub struct Day10;
+
+impl Solver for Day10 {
+    type Input = Program;
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Program, Box<dyn Error>> {
+        let mut instructions = Vec::new();
+        for (i, line) in input.lines().enumerate() {
+            let command = line?;
+            let (instruction, v) = command.split_once(' ').unwrap_or((&command, ""));
+            match instruction {
+                "addx" => instructions.push(Instruction::Addx(parse_field::<i32>(i, &command, v)?)),
+                "noop" => instructions.push(Instruction::Noop),
+                _ => return Err(ParseError::at(i, &command, instruction, "expected `addx` or `noop`").into()),
             }
-            "noop" => cycle += 1,
-            _ => return Err(format!("malformed input at line {}", i + 1).into()),
         }
+        Ok(Program(instructions))
     }
-    update(&mut signal_strength, &mut crt_screen, x, cycle);
-    let mut crt = String::new();
-    for (i, row) in crt_screen.into_iter().enumerate() {
-        if i != 0 {
-            writeln!(crt)?;
+
+    fn part_1(&self, program: &Program) -> Result<Answer, Box<dyn Error>> {
+        let mut signal_strength = 0;
+        for (i, x) in program.register_values().into_iter().enumerate() {
+            let cycle = i + 1;
+            if let 20 | 60 | 100 | 140 | 180 | 220 = cycle {
+                signal_strength += x * (cycle as i32);
+            }
         }
-        for p in row {
-            write!(crt, "{}", if p { '#' } else { '.' })?;
+        Ok(signal_strength.into())
+    }
+
+    fn part_2(&self, program: &Program) -> Result<Answer, Box<dyn Error>> {
+        let mut crt_screen = [[false; 40]; 6];
+        for (i, x) in program.register_values().into_iter().take(240).enumerate() {
+            let (row, pos) = (i / 40, i % 40);
+            crt_screen[row][pos] = (x - (pos as i32)).abs() <= 1;
         }
+        let crt = crt_screen
+            .into_iter()
+            .map(|row| row.into_iter().map(|p| if p { '#' } else { '.' }).collect())
+            .collect::<Vec<String>>();
+        Ok(crt.into())
     }
-    Ok((Box::new(signal_strength), Box::new(crt)))
 }
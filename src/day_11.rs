@@ -1,12 +1,11 @@
-use crate::*;
+use crate::common::*;
 use itertools::Itertools;
 use std::{
     cell::RefCell,
     collections::{vec_deque, BinaryHeap, VecDeque},
     error::Error,
     fmt::Display,
-    fs::File,
-    io::{BufRead, BufReader},
+    io::BufRead,
     rc::Rc,
     str::FromStr,
 };
@@ -63,12 +62,17 @@ impl Clone for Monkey {
 }
 
 impl FromStr for Monkey {
-    type Err = Box<dyn Error>;
+    type Err = ParseError;
 
+    // Line numbers in the error are relative to the start of `s`.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let lines = s.lines().collect::<Vec<_>>();
         if lines.len() != 6 {
-            return Err(format!("malformed input:\n{s}").into());
+            return Err(ParseError::line(
+                0,
+                lines.first().unwrap_or(&""),
+                format!("expected 6 lines for each monkey, found {}", lines.len()),
+            ));
         }
 
         let items = RefCell::new(
@@ -77,7 +81,7 @@ impl FromStr for Monkey {
                 .trim_start_matches("Starting items: ")
                 .split(", ")
                 .into_iter()
-                .map(|n| n.parse::<usize>())
+                .map(|n| parse_field::<usize>(1, lines[1], n))
                 .collect::<Result<Items, _>>()?,
         );
 
@@ -95,22 +99,26 @@ impl FromStr for Monkey {
             .split(' ')
             .collect::<Vec<_>>();
         if operation_parts.len() != 3 {
-            return Err(format!("malformed input:\n{s}").into());
+            return Err(ParseError::line(
+                2,
+                lines[2],
+                "expected an operation like `new = old * 19`",
+            ));
         }
         let operation_lhs = if operation_parts[0] == "old" {
             OperationArg::Old
         } else {
-            OperationArg::Constant(operation_parts[0].parse()?)
+            OperationArg::Constant(parse_field(2, lines[2], operation_parts[0])?)
         };
         let operation_op = match operation_parts[1] {
             "+" => OperationType::Add,
             "*" => OperationType::Multiply,
-            _ => return Err(format!("malformed input:\n{s}").into()),
+            _ => return Err(ParseError::at(2, lines[2], operation_parts[1], "expected `+` or `*`")),
         };
         let operation_rhs = if operation_parts[2] == "old" {
             OperationArg::Old
         } else {
-            OperationArg::Constant(operation_parts[2].parse()?)
+            OperationArg::Constant(parse_field(2, lines[2], operation_parts[2])?)
         };
         let operation = Rc::new(move |n| {
             let lhs = match operation_lhs {
@@ -127,18 +135,21 @@ impl FromStr for Monkey {
             }
         });
 
-        let test_divisible_by = lines[3]
-            .trim_start()
-            .trim_start_matches("Test: divisible by ")
-            .parse::<usize>()?;
-        let test_if_true = lines[4]
-            .trim_start()
-            .trim_start_matches("If true: throw to monkey ")
-            .parse::<usize>()?;
-        let test_if_false = lines[5]
-            .trim_start()
-            .trim_start_matches("If false: throw to monkey ")
-            .parse::<usize>()?;
+        let test_divisible_by = parse_field::<usize>(
+            3,
+            lines[3],
+            lines[3].trim_start().trim_start_matches("Test: divisible by "),
+        )?;
+        let test_if_true = parse_field::<usize>(
+            4,
+            lines[4],
+            lines[4].trim_start().trim_start_matches("If true: throw to monkey "),
+        )?;
+        let test_if_false = parse_field::<usize>(
+            5,
+            lines[5],
+            lines[5].trim_start().trim_start_matches("If false: throw to monkey "),
+        )?;
         let test = Rc::new(move |n| {
             if n % test_divisible_by == 0 {
                 test_if_true
@@ -157,7 +168,7 @@ impl FromStr for Monkey {
 }
 
 #[derive(Clone)]
-struct KeepAway {
+pub struct KeepAway {
     monkeys: Vec<Monkey>,
     m: usize,
 }
@@ -181,14 +192,15 @@ impl Display for KeepAway {
 }
 
 impl KeepAway {
-    fn simulate_round(&mut self, manageable_worry: bool) {
+    // Plays a round, dividing the worry levels by `relief` after every
+    // inspection if given, or keeping them manageable by other means otherwise.
+    fn simulate_round(&mut self, relief: Option<usize>) {
         for monkey in self.monkeys.iter() {
             while let Some(mut item) = monkey.items.borrow_mut().pop_front() {
                 item = (monkey.operation)(item);
-                if manageable_worry {
-                    item /= 3;
-                } else {
-                    item %= self.m;
+                match relief {
+                    Some(relief) => item /= relief,
+                    None => item %= self.m,
                 }
                 let idx = (monkey.test)(item);
                 self.monkeys[idx].items.borrow_mut().push_back(item);
@@ -208,32 +220,102 @@ impl KeepAway {
     }
 }
 
-pub fn solve(input: File) -> Result<Solution, Box<dyn Error>> {
-    let reader = BufReader::new(input);
-    let mut keep_away_manageable = KeepAway {
-        monkeys: Vec::new(),
-        m: 0,
-    };
-    for mut line in reader
-        .lines()
-        .collect::<Result<Vec<_>, _>>()?
-        .into_iter()
-        .filter(|line| !line.is_empty())
-        .chunks(6)
-        .into_iter()
-    {
-        keep_away_manageable.monkeys.push(Monkey::from_str(&line.join("\n"))?);
+const ROUNDS_1: Param = Param {
+    name: "rounds_1",
+    default: "20",
+    description: "The number of rounds to play in part 1",
+};
+const ROUNDS_2: Param = Param {
+    name: "rounds_2",
+    default: "10000",
+    description: "The number of rounds to play in part 2",
+};
+const RELIEF: Param = Param {
+    name: "relief",
+    default: "3",
+    description: "The divisor of the worry levels after every inspection in part 1",
+};
+
+pub struct Day11 {
+    cancel: CancellationToken,
+    rounds_1: usize,
+    rounds_2: usize,
+    relief: usize,
+}
+
+impl Default for Day11 {
+    fn default() -> Self {
+        Self {
+            cancel: CancellationToken::default(),
+            rounds_1: ROUNDS_1.default_value(),
+            rounds_2: ROUNDS_2.default_value(),
+            relief: RELIEF.default_value(),
+        }
+    }
+}
+
+impl Solver for Day11 {
+    type Input = KeepAway;
+
+    const PARAMS: &'static [Param] = &[ROUNDS_1, ROUNDS_2, RELIEF];
+
+    fn set_cancellation(&mut self, cancel: CancellationToken) {
+        self.cancel = cancel;
+    }
+
+    fn set_params(&mut self, params: &Params) -> Result<(), Box<dyn Error>> {
+        self.rounds_1 = params.get(&ROUNDS_1)?;
+        self.rounds_2 = params.get(&ROUNDS_2)?;
+        self.relief = params.get(&RELIEF)?;
+        Ok(())
+    }
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<KeepAway, Box<dyn Error>> {
+        let mut keep_away = KeepAway {
+            monkeys: Vec::new(),
+            m: 0,
+        };
+        for chunk in input
+            .lines()
+            .enumerate()
+            .map(|(i, line)| line.map(|line| (i, line)))
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .filter(|(_, line)| !line.is_empty())
+            .chunks(6)
+            .into_iter()
+        {
+            let (indices, lines): (Vec<_>, Vec<_>) = chunk.unzip();
+            let monkey = Monkey::from_str(&lines.join("\n")).map_err(|err| ParseError {
+                line: indices[err.line - 1] + 1,
+                ..err
+            })?;
+            keep_away.monkeys.push(monkey);
+        }
+        keep_away.m = keep_away.monkeys.iter().map(|monkey| monkey.n).product();
+        Ok(keep_away)
     }
-    keep_away_manageable.m = keep_away_manageable.monkeys.iter().map(|monkey| monkey.n).product();
-    let mut keep_away_unmanageable = keep_away_manageable.clone();
-    for _ in 0..20 {
-        keep_away_manageable.simulate_round(true);
+
+    fn part_1(&self, keep_away: &KeepAway) -> Result<Answer, Box<dyn Error>> {
+        // A relief of 1 would never reduce the worry levels, which then overflow.
+        // Only part 1 uses it, so part 2 can still be solved on its own.
+        if self.relief < 2 {
+            return Err(Box::from("the relief must be at least 2"));
+        }
+        let mut keep_away_manageable = keep_away.clone();
+        for _ in 0..self.rounds_1 {
+            self.cancel.check()?;
+            keep_away_manageable.simulate_round(Some(self.relief));
+        }
+        Ok(keep_away_manageable.monkey_business()?.into())
     }
-    for _ in 0..10000 {
-        keep_away_unmanageable.simulate_round(false);
+
+    fn part_2(&self, keep_away: &KeepAway) -> Result<Answer, Box<dyn Error>> {
+        let mut keep_away_unmanageable = keep_away.clone();
+        for _ in 0..self.rounds_2 {
+            self.cancel.check()?;
+            keep_away_unmanageable.simulate_round(None);
+        }
+        Ok(keep_away_unmanageable.monkey_business()?.into())
     }
-    Ok((
-        Box::new(keep_away_manageable.monkey_business()?),
-        Box::new(keep_away_unmanageable.monkey_business()?),
-    ))
 }
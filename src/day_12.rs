@@ -1,12 +1,16 @@
-use crate::*;
+use crate::common::*;
 use std::{
     collections::{HashMap, HashSet},
     error::Error,
-    fs::File,
-    io::{BufRead, BufReader},
+    io::BufRead,
 };
 
-fn a_star(start: &[(usize, usize)], end: (usize, usize), height_map: &Vec<Vec<u8>>) -> usize {
+fn a_star(
+    start: &[(usize, usize)],
+    end: (usize, usize),
+    height_map: &Vec<Vec<u8>>,
+    cancel: &CancellationToken,
+) -> Result<usize, Box<dyn Error>> {
     let rows = height_map.len();
     let cols = height_map[0].len();
     let h = |n: &(usize, usize)| end.0.abs_diff(n.0) + end.1.abs_diff(n.1);
@@ -23,6 +27,7 @@ fn a_star(start: &[(usize, usize)], end: (usize, usize), height_map: &Vec<Vec<u8
     }
 
     while !open_set.is_empty() {
+        cancel.check()?;
         let current = *open_set
             .iter()
             .min_by(|&x, &y| {
@@ -68,43 +73,72 @@ fn a_star(start: &[(usize, usize)], end: (usize, usize), height_map: &Vec<Vec<u8
         }
     }
 
-    *g_scores.get(&end).unwrap()
+    Ok(*g_scores.get(&end).unwrap())
 }
 
-pub fn solve(input: File) -> Result<Solution, Box<dyn Error>> {
-    let reader = BufReader::new(input);
-    let mut height_map: Vec<Vec<u8>> = Vec::new();
-    let (mut start, mut end) = ((0, 0), (0, 0));
-    let mut all_starts = Vec::new();
-    for line in reader.lines() {
-        let row = line?;
-        height_map.push(
-            row.chars()
-                .into_iter()
-                .enumerate()
-                .map(|(i, c)| {
-                    let h = match c {
-                        'S' => {
-                            start = (height_map.len(), i);
-                            0
-                        }
-                        'E' => {
-                            end = (height_map.len(), i);
-                            25
+pub struct HeightMap {
+    pub heights: Vec<Vec<u8>>,
+    pub start: (usize, usize),
+    pub end: (usize, usize),
+    pub all_starts: Vec<(usize, usize)>,
+}
+
+#[derive(Default)]
+pub struct Day12 {
+    cancel: CancellationToken,
+}
+
+impl Solver for Day12 {
+    type Input = HeightMap;
+
+    fn set_cancellation(&mut self, cancel: CancellationToken) {
+        self.cancel = cancel;
+    }
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<HeightMap, Box<dyn Error>> {
+        let mut height_map: Vec<Vec<u8>> = Vec::new();
+        let (mut start, mut end) = ((0, 0), (0, 0));
+        let mut all_starts = Vec::new();
+        for line in input.lines() {
+            let row = line?;
+            height_map.push(
+                row.chars()
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, c)| {
+                        let h = match c {
+                            'S' => {
+                                start = (height_map.len(), i);
+                                0
+                            }
+                            'E' => {
+                                end = (height_map.len(), i);
+                                25
+                            }
+                            _ => (c as u8) - 97, // Map ASCII codes 97-122 => 0-25.
+                        };
+                        if h == 0 {
+                            all_starts.push((height_map.len(), i));
                         }
-                        _ => (c as u8) - 97, // Map ASCII codes 97-122 => 0-25.
-                    };
-                    if h == 0 {
-                        all_starts.push((height_map.len(), i));
-                    }
-                    h
-                })
-                .collect(),
-        );
+                        h
+                    })
+                    .collect(),
+            );
+        }
+
+        Ok(HeightMap {
+            heights: height_map,
+            start,
+            end,
+            all_starts,
+        })
     }
 
-    Ok((
-        Box::new(a_star(&[start], end, &height_map)),
-        Box::new(a_star(&all_starts, end, &height_map)),
-    ))
+    fn part_1(&self, map: &HeightMap) -> Result<Answer, Box<dyn Error>> {
+        Ok(a_star(&[map.start], map.end, &map.heights, &self.cancel)?.into())
+    }
+
+    fn part_2(&self, map: &HeightMap) -> Result<Answer, Box<dyn Error>> {
+        Ok(a_star(&map.all_starts, map.end, &map.heights, &self.cancel)?.into())
+    }
 }
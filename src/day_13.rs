@@ -1,16 +1,10 @@
 use itertools::{EitherOrBoth, Itertools};
 
-use crate::*;
-use std::{
-    cmp::Ordering,
-    error::Error,
-    fs::File,
-    io::{BufRead, BufReader},
-    str::FromStr,
-};
+use crate::common::*;
+use std::{cmp::Ordering, error::Error, io::BufRead, str::FromStr};
 
 #[derive(PartialEq, Eq, Clone)]
-enum Packet {
+pub enum Packet {
     Integer(u8),
     List(Vec<Packet>),
 }
@@ -78,39 +72,62 @@ impl FromStr for Packet {
     }
 }
 
-pub fn solve(input: File) -> Result<Solution, Box<dyn Error>> {
-    let reader = BufReader::new(input);
-    let mut lines = reader.lines();
-    let mut pairs = Vec::new();
-    while let Some(line) = lines.next() {
-        let l = line?;
-        if l.is_empty() {
-            continue;
+pub struct DistressSignal(pub Vec<(Packet, Packet)>);
+
+#[derive(Default)]
+pub struct Day13;
+
+impl Solver for Day13 {
+    type Input = DistressSignal;
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<DistressSignal, Box<dyn Error>> {
+        let mut lines = input.lines().enumerate();
+        let mut pairs = Vec::new();
+        let packet = |i, l: &str| {
+            l.parse::<Packet>()
+                .map_err(|err| ParseError::line(i, l, err.to_string()))
+        };
+        while let Some((i, line)) = lines.next() {
+            let l = line?;
+            if l.is_empty() {
+                continue;
+            }
+            let (j, line) = lines
+                .next()
+                .ok_or_else(|| ParseError::line(i, &l, "expected another packet on the next line"))?;
+            let l2 = line?;
+            pairs.push((packet(i, &l)?, packet(j, &l2)?));
         }
-        let l2 = lines.next().ok_or_else(|| format!("expected line after {l}"))??;
-        pairs.push((l.parse::<Packet>()?, l2.parse::<Packet>()?));
+        Ok(DistressSignal(pairs))
     }
 
-    let in_order_total: usize = pairs
-        .iter()
-        .enumerate()
-        .filter_map(|(i, (lhs, rhs))| if lhs < rhs { Some(i + 1) } else { None })
-        .sum();
+    fn part_1(&self, signal: &DistressSignal) -> Result<Answer, Box<dyn Error>> {
+        let in_order_total: usize = signal
+            .0
+            .iter()
+            .enumerate()
+            .filter_map(|(i, (lhs, rhs))| if lhs < rhs { Some(i + 1) } else { None })
+            .sum();
+        Ok(in_order_total.into())
+    }
 
-    let divider_packet_1 = Packet::List(vec![Packet::List(vec![Packet::Integer(2)])]);
-    let divider_packet_2 = Packet::List(vec![Packet::List(vec![Packet::Integer(6)])]);
-    let mut all_packets = pairs
-        .iter()
-        .flat_map(|pair| [pair.0.clone(), pair.1.clone()])
-        .chain([divider_packet_1.clone(), divider_packet_2.clone()])
-        .collect::<Vec<_>>();
-    all_packets.sort();
-    let mut decoder_key = 1;
-    for (i, packet) in all_packets.into_iter().enumerate() {
-        if packet == divider_packet_1 || packet == divider_packet_2 {
-            decoder_key *= i + 1;
+    fn part_2(&self, signal: &DistressSignal) -> Result<Answer, Box<dyn Error>> {
+        let divider_packet_1 = Packet::List(vec![Packet::List(vec![Packet::Integer(2)])]);
+        let divider_packet_2 = Packet::List(vec![Packet::List(vec![Packet::Integer(6)])]);
+        let mut all_packets = signal
+            .0
+            .iter()
+            .flat_map(|pair| [pair.0.clone(), pair.1.clone()])
+            .chain([divider_packet_1.clone(), divider_packet_2.clone()])
+            .collect::<Vec<_>>();
+        all_packets.sort();
+        let mut decoder_key = 1;
+        for (i, packet) in all_packets.into_iter().enumerate() {
+            if packet == divider_packet_1 || packet == divider_packet_2 {
+                decoder_key *= i + 1;
+            }
         }
-    }
 
-    Ok((Box::new(in_order_total), Box::new(decoder_key)))
+        Ok(decoder_key.into())
+    }
 }
@@ -1,11 +1,10 @@
 use itertools::Itertools;
 
-use crate::*;
+use crate::common::*;
 use std::{
     cmp::{max, min},
     error::Error,
-    fs::File,
-    io::{BufRead, BufReader},
+    io::BufRead,
 };
 
 #[inline]
@@ -14,7 +13,7 @@ const fn map_col(col: usize) -> usize {
 }
 
 #[derive(Clone)]
-struct Cave {
+pub struct Cave {
     grid: [[bool; 400]; 200], // Columns range from 300-700.
     sand_source: (usize, usize),
     lowest_rock: usize,
@@ -91,24 +90,46 @@ impl Cave {
     }
 }
 
-pub fn solve(input: File) -> Result<Solution, Box<dyn Error>> {
-    let reader = BufReader::new(input);
-    let mut cave: Cave = Default::default();
-    for line in reader.lines() {
-        cave.add_line(&line?)?;
+#[derive(Default)]
+pub struct Day14 {
+    cancel: CancellationToken,
+}
+
+impl Solver for Day14 {
+    type Input = Cave;
+
+    fn set_cancellation(&mut self, cancel: CancellationToken) {
+        self.cancel = cancel;
     }
-    let mut cave_with_floor = cave.clone();
-    cave_with_floor.add_floor();
 
-    let mut num_sand = 0;
-    while cave.add_sand() {
-        num_sand += 1;
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Cave, Box<dyn Error>> {
+        let mut cave: Cave = Default::default();
+        for (i, line) in input.lines().enumerate() {
+            let l = line?;
+            cave.add_line(&l)
+                .map_err(|err| ParseError::line(i, &l, err.to_string()))?;
+        }
+        Ok(cave)
     }
 
-    let mut num_sand_with_floor = 0;
-    while cave_with_floor.add_sand() {
-        num_sand_with_floor += 1;
+    fn part_1(&self, cave: &Cave) -> Result<Answer, Box<dyn Error>> {
+        let mut cave = cave.clone();
+        let mut num_sand = 0;
+        while cave.add_sand() {
+            self.cancel.check()?;
+            num_sand += 1;
+        }
+        Ok(num_sand.into())
     }
 
-    Ok((Box::new(num_sand), Box::new(num_sand_with_floor)))
+    fn part_2(&self, cave: &Cave) -> Result<Answer, Box<dyn Error>> {
+        let mut cave_with_floor = cave.clone();
+        cave_with_floor.add_floor();
+        let mut num_sand_with_floor = 0;
+        while cave_with_floor.add_sand() {
+            self.cancel.check()?;
+            num_sand_with_floor += 1;
+        }
+        Ok(num_sand_with_floor.into())
+    }
 }
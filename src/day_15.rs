@@ -1,15 +1,8 @@
-use crate::*;
-use std::{
-    collections::HashSet,
-    error::Error,
-    fmt::Debug,
-    fs::File,
-    io::{BufRead, BufReader},
-    str::FromStr,
-};
+use crate::common::*;
+use std::{collections::HashSet, error::Error, fmt::Debug, io::BufRead, str::FromStr};
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
-struct Position(i64, i64);
+pub struct Position(pub i64, pub i64);
 
 impl FromStr for Position {
     type Err = Box<dyn Error>;
@@ -30,15 +23,15 @@ impl Debug for Position {
 }
 
 impl Position {
-    const fn manhattan(&self, other: &Self) -> u64 {
+    pub const fn manhattan(&self, other: &Self) -> u64 {
         self.0.abs_diff(other.0) + self.1.abs_diff(other.1)
     }
 }
 
-struct Sensor {
-    pos: Position,
-    beacon: Position,
-    beacon_dist: u64,
+pub struct Sensor {
+    pub pos: Position,
+    pub beacon: Position,
+    pub beacon_dist: u64,
 }
 
 impl FromStr for Sensor {
@@ -59,68 +52,127 @@ impl FromStr for Sensor {
     }
 }
 
-pub fn solve(input: File) -> Result<Solution, Box<dyn Error>> {
-    let reader = BufReader::new(input);
-    let mut sensors = Vec::new();
-    for line in reader.lines() {
-        let sensor_reading = line?;
-        sensors.push(sensor_reading.parse::<Sensor>()?);
+pub struct Sensors(pub Vec<Sensor>);
+
+// The example uses row 10 and a bound of 20.
+const ROW: Param = Param {
+    name: "row",
+    default: "2000000",
+    description: "The row to count the positions where a beacon cannot be in",
+};
+const BOUND: Param = Param {
+    name: "bound",
+    default: "4000000",
+    description: "The maximum x and y coordinate of the distress beacon",
+};
+
+pub struct Day15 {
+    cancel: CancellationToken,
+    row: i64,
+    bound: i64,
+}
+
+impl Default for Day15 {
+    fn default() -> Self {
+        Self {
+            cancel: CancellationToken::default(),
+            row: ROW.default_value(),
+            bound: BOUND.default_value(),
+        }
+    }
+}
+
+impl Solver for Day15 {
+    type Input = Sensors;
+
+    const PARAMS: &'static [Param] = &[ROW, BOUND];
+
+    fn set_cancellation(&mut self, cancel: CancellationToken) {
+        self.cancel = cancel;
     }
 
-    const ROW: i64 = 2000000;
-    let mut row_known_beacons = HashSet::new();
-    let mut beacon_not_possible_positions = HashSet::new();
-    for sensor in sensors.iter() {
-        if sensor.beacon.1 == ROW {
-            row_known_beacons.insert(sensor.beacon);
+    fn set_params(&mut self, params: &Params) -> Result<(), Box<dyn Error>> {
+        self.row = params.get(&ROW)?;
+        self.bound = params.get(&BOUND)?;
+        if self.bound < 0 {
+            return Err(Box::from("the bound must not be negative"));
         }
-        let vertical_distance = sensor.pos.1.abs_diff(ROW);
-        if vertical_distance > sensor.beacon_dist {
-            continue;
+        Ok(())
+    }
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Sensors, Box<dyn Error>> {
+        let mut sensors = Vec::new();
+        for (i, line) in input.lines().enumerate() {
+            let sensor_reading = line?;
+            sensors.push(
+                sensor_reading
+                    .parse::<Sensor>()
+                    .map_err(|err| ParseError::line(i, &sensor_reading, err.to_string()))?,
+            );
         }
-        let horizontal_distance = (sensor.beacon_dist - vertical_distance) as i64;
-        for i in 0..=horizontal_distance {
-            beacon_not_possible_positions.insert(Position(sensor.pos.0 + i, ROW));
-            beacon_not_possible_positions.insert(Position(sensor.pos.0 - i, ROW));
+        Ok(Sensors(sensors))
+    }
+
+    fn part_1(&self, sensors: &Sensors) -> Result<Answer, Box<dyn Error>> {
+        let mut row_known_beacons = HashSet::new();
+        let mut beacon_not_possible_positions = HashSet::new();
+        let row = self.row;
+        for sensor in sensors.0.iter() {
+            self.cancel.check()?;
+            if sensor.beacon.1 == row {
+                row_known_beacons.insert(sensor.beacon);
+            }
+            let vertical_distance = sensor.pos.1.abs_diff(row);
+            if vertical_distance > sensor.beacon_dist {
+                continue;
+            }
+            let horizontal_distance = (sensor.beacon_dist - vertical_distance) as i64;
+            for i in 0..=horizontal_distance {
+                beacon_not_possible_positions.insert(Position(sensor.pos.0 + i, row));
+                beacon_not_possible_positions.insert(Position(sensor.pos.0 - i, row));
+            }
         }
+        beacon_not_possible_positions.drain_filter(|pos| row_known_beacons.contains(pos));
+        Ok(beacon_not_possible_positions.len().into())
     }
-    beacon_not_possible_positions.drain_filter(|pos| row_known_beacons.contains(pos));
-
-    let mut distress_pos = Position(-1, -1);
-    const DX: [i64; 4] = [-1, 1, 1, -1];
-    const DY: [i64; 4] = [1, 1, -1, -1];
-    for sensor in sensors.iter() {
-        // Go around the perimeter one point at a time, borrowed from
-        // https://www.reddit.com/r/adventofcode/comments/zmi9n4/comment/j0d9nnv/?utm_source=share&utm_medium=web2x&context=3
-        let mut perimeter_pos = sensor.pos;
-        perimeter_pos.1 -= sensor.beacon_dist as i64 + 1;
-        for (dx, dy) in DX.zip(DY) {
-            'outer: for _ in 0..=sensor.beacon_dist {
-                if perimeter_pos.0 < 0
-                    || perimeter_pos.0 > (ROW * 2)
-                    || perimeter_pos.1 < 0
-                    || perimeter_pos.1 > (ROW * 2)
-                {
-                    perimeter_pos.0 += dx;
-                    perimeter_pos.1 += dy;
-                    continue 'outer;
-                }
-                for sensor in sensors.iter() {
-                    if sensor.pos.manhattan(&perimeter_pos) <= sensor.beacon_dist {
+
+    fn part_2(&self, sensors: &Sensors) -> Result<Answer, Box<dyn Error>> {
+        let mut distress_pos = Position(-1, -1);
+        const DX: [i64; 4] = [-1, 1, 1, -1];
+        const DY: [i64; 4] = [1, 1, -1, -1];
+        for sensor in sensors.0.iter() {
+            // Go around the perimeter one point at a time, borrowed from
+            // https://www.reddit.com/r/adventofcode/comments/zmi9n4/comment/j0d9nnv/?utm_source=share&utm_medium=web2x&context=3
+            let mut perimeter_pos = sensor.pos;
+            perimeter_pos.1 -= sensor.beacon_dist as i64 + 1;
+            for (dx, dy) in DX.zip(DY) {
+                self.cancel.check()?;
+                'outer: for _ in 0..=sensor.beacon_dist {
+                    if perimeter_pos.0 < 0
+                        || perimeter_pos.0 > self.bound
+                        || perimeter_pos.1 < 0
+                        || perimeter_pos.1 > self.bound
+                    {
                         perimeter_pos.0 += dx;
                         perimeter_pos.1 += dy;
                         continue 'outer;
                     }
+                    for sensor in sensors.0.iter() {
+                        if sensor.pos.manhattan(&perimeter_pos) <= sensor.beacon_dist {
+                            perimeter_pos.0 += dx;
+                            perimeter_pos.1 += dy;
+                            continue 'outer;
+                        }
+                    }
+                    distress_pos = perimeter_pos;
+                    break;
                 }
-                distress_pos = perimeter_pos;
-                break;
             }
         }
+        if distress_pos == Position(-1, -1) {
+            return Err(Box::from("no position for the distress beacon within the bound"));
+        }
+        let tuning_frequency = (distress_pos.0 * 4000000) + distress_pos.1;
+        Ok(tuning_frequency.into())
     }
-    let tuning_frequency = (distress_pos.0 * 4000000) + distress_pos.1;
-
-    Ok((
-        Box::new(beacon_not_possible_positions.len()),
-        Box::new(tuning_frequency),
-    ))
 }
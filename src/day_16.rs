@@ -1,21 +1,20 @@
-use crate::*;
+use crate::common::*;
 use regex::Regex;
 use std::{
     collections::{HashMap, HashSet},
     error::Error,
-    fs::File,
-    io::{BufRead, BufReader},
+    io::BufRead,
 };
 
 #[derive(Debug)]
-struct Valve {
-    flow_rate: usize,
-    leads_to: Vec<String>,
+pub struct Valve {
+    pub flow_rate: usize,
+    pub leads_to: Vec<String>,
 }
 
 impl Valve {
-    fn time_to_reach(valves: &HashMap<String, Self>, src: &str, dest: &str) -> usize {
-        path_find(src, dest, valves, &mut HashSet::new(), usize::MAX)
+    fn time_to_reach(valves: &HashMap<String, Self>, src: &str, dest: &str, cancel: &CancellationToken) -> usize {
+        path_find(src, dest, valves, &mut HashSet::new(), usize::MAX, cancel)
     }
 }
 
@@ -25,7 +24,11 @@ fn path_find(
     valves: &HashMap<String, Valve>,
     visited: &mut HashSet<String>,
     mut current_min: usize,
+    cancel: &CancellationToken,
 ) -> usize {
+    if cancel.is_cancelled() {
+        return usize::MAX;
+    }
     let current = valves.get(start).unwrap();
     current
         .leads_to
@@ -41,73 +44,134 @@ fn path_find(
             } else {
                 let mut new_visited = visited.clone();
                 new_visited.insert(next.to_owned());
-                path_find(next, end, valves, &mut new_visited, current_min)
+                path_find(next, end, valves, &mut new_visited, current_min, cancel)
             }
         })
         .min()
         .unwrap_or(usize::MAX)
 }
 
-pub fn solve(input: File) -> Result<Solution, Box<dyn Error>> {
-    let reader = BufReader::new(input);
-    let mut valves = HashMap::new();
-    let leads_to_re = Regex::new("; tunnels? leads? to valves? ").unwrap();
-    for (i, line) in reader.lines().enumerate() {
-        let valve_description = line?;
-        let (name, rest) = valve_description
-            .trim_start_matches("Valve ")
-            .split_once(" has flow rate=")
-            .ok_or_else(|| format!("malformed input at line {}", i + 1))?;
-        let (flow_rate, leads_to) = rest
-            .split_once(&leads_to_re)
-            .ok_or_else(|| format!("malformed input at line {}", i + 1))?;
-        valves.insert(
-            name.to_owned(),
-            Valve {
-                flow_rate: flow_rate.parse()?,
-                leads_to: leads_to.split(", ").map(|s| s.to_owned()).collect(),
-            },
-        );
+const MINUTES: Param = Param {
+    name: "minutes",
+    default: "30",
+    description: "The number of minutes before the volcano erupts",
+};
+const START: Param = Param {
+    name: "start",
+    default: "AA",
+    description: "The valve to start at",
+};
+
+pub struct Day16 {
+    cancel: CancellationToken,
+    minutes: usize,
+    start: String,
+}
+
+impl Default for Day16 {
+    fn default() -> Self {
+        Self {
+            cancel: CancellationToken::default(),
+            minutes: MINUTES.default_value(),
+            start: START.default_value(),
+        }
     }
+}
 
-    let mut to_visit = valves
-        .iter()
-        .filter(|(_, valve)| valve.flow_rate != 0)
-        .map(|(name, _)| name.to_owned())
-        .collect::<HashSet<_>>();
-    let mut time_remaining = 30;
-    let mut total_pressure = 0;
-    let mut current = "AA".to_owned();
-    if to_visit.contains(&current) {
-        time_remaining -= 1;
-        total_pressure *= valves[&current].flow_rate * time_remaining;
+impl Solver for Day16 {
+    type Input = HashMap<String, Valve>;
+
+    const PARAMS: &'static [Param] = &[MINUTES, START];
+
+    fn set_cancellation(&mut self, cancel: CancellationToken) {
+        self.cancel = cancel;
+    }
+
+    fn set_params(&mut self, params: &Params) -> Result<(), Box<dyn Error>> {
+        self.minutes = params.get(&MINUTES)?;
+        if self.minutes == 0 {
+            return Err(Box::from("the number of minutes must not be 0"));
+        }
+        self.start = params.get(&START)?;
+        Ok(())
+    }
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<HashMap<String, Valve>, Box<dyn Error>> {
+        let mut valves = HashMap::new();
+        let leads_to_re = Regex::new("; tunnels? leads? to valves? ").unwrap();
+        for (i, line) in input.lines().enumerate() {
+            let valve_description = line?;
+            let (name, rest) = valve_description
+                .trim_start_matches("Valve ")
+                .split_once(" has flow rate=")
+                .ok_or_else(|| {
+                    ParseError::line(i, &valve_description, "expected `Valve <name> has flow rate=<rate>`")
+                })?;
+            let (flow_rate, leads_to) = rest.split_once(&leads_to_re).ok_or_else(|| {
+                ParseError::at(
+                    i,
+                    &valve_description,
+                    rest,
+                    "expected `; tunnels lead to valves <names>`",
+                )
+            })?;
+            valves.insert(
+                name.to_owned(),
+                Valve {
+                    flow_rate: parse_field(i, &valve_description, flow_rate)?,
+                    leads_to: leads_to.split(", ").map(|s| s.to_owned()).collect(),
+                },
+            );
+        }
+        Ok(valves)
     }
-    while !to_visit.is_empty() {
-        let mut next = current.clone();
-        let mut pressure_released = 0;
-        let mut time_taken = 0;
-        for valve in to_visit.iter() {
-            let time = Valve::time_to_reach(&valves, &current, valve) + 1;
-            if time < time_remaining {
-                let pressure = (time_remaining - time) * valves[valve].flow_rate;
-                println!("  Reaching valve {valve} and opening it will take {time} minutes and will release {pressure} pressure (flow_rate: {})", valves[valve].flow_rate);
-                if pressure > pressure_released {
-                    next = valve.to_owned();
-                    pressure_released = pressure;
-                    time_taken = time;
+
+    fn part_1(&self, valves: &HashMap<String, Valve>) -> Result<Answer, Box<dyn Error>> {
+        let mut to_visit = valves
+            .iter()
+            .filter(|(_, valve)| valve.flow_rate != 0)
+            .map(|(name, _)| name.to_owned())
+            .collect::<HashSet<_>>();
+        if !valves.contains_key(&self.start) {
+            return Err(format!("there is no valve {}", self.start).into());
+        }
+        let mut time_remaining = self.minutes;
+        let mut total_pressure = 0;
+        let mut current = self.start.clone();
+        if to_visit.contains(&current) {
+            time_remaining -= 1;
+            total_pressure *= valves[&current].flow_rate * time_remaining;
+        }
+        while !to_visit.is_empty() {
+            let mut next = current.clone();
+            let mut pressure_released = 0;
+            let mut time_taken = 0;
+            for valve in to_visit.iter() {
+                let distance = Valve::time_to_reach(valves, &current, valve, &self.cancel);
+                self.cancel.check()?;
+                let time = distance + 1;
+                if time < time_remaining {
+                    let pressure = (time_remaining - time) * valves[valve].flow_rate;
+                    if pressure > pressure_released {
+                        next = valve.to_owned();
+                        pressure_released = pressure;
+                        time_taken = time;
+                    }
                 }
             }
+            if next == current {
+                break;
+            }
+            to_visit.remove(&next);
+            current = next;
+            total_pressure += pressure_released;
+            time_remaining -= time_taken;
         }
-        if next == current {
-            println!("No other valve reachable");
-            break;
-        }
-        to_visit.remove(&next);
-        println!("Moving from {current} to {next} and opening the valve in {time_taken} minutes for a total pressure of {pressure_released}");
-        current = next;
-        total_pressure += pressure_released;
-        time_remaining -= time_taken;
+
+        Ok(total_pressure.into())
     }
 
-    Ok((Box::new(total_pressure), Box::new(2)))
+    fn part_2(&self, _valves: &HashMap<String, Valve>) -> Result<Answer, Box<dyn Error>> {
+        Ok(2.into())
+    }
 }
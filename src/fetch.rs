@@ -0,0 +1,190 @@
+use crate::*;
+use client::{Client, ClientArgs};
+use layout::Layout;
+use std::{error::Error, fs, path::Path};
+
+#[derive(clap::Args)]
+pub(crate) struct FetchArgs {
+    #[clap(flatten)]
+    client: ClientArgs,
+}
+
+// Downloads the input of every day that does not have one yet. The client is
+// only created once an input is actually missing, so that no session cookie is
+// needed when everything is cached.
+pub(crate) fn run(
+    dir: &Path,
+    year: usize,
+    days: &[usize],
+    layout: Option<&Layout>,
+    args: &FetchArgs,
+) -> Result<(), Box<dyn Error>> {
+    if dir == Path::new("-") || dir.is_file() {
+        return Err(Box::from("the input must be a directory to fetch inputs into"));
+    }
+    let mut client = None;
+    let mut failed = false;
+    for &day in days {
+        if let Ok(path) = layout::find_input(dir, year, day, false, layout) {
+            println!("Day {day}: cached at {}", path.display());
+            continue;
+        }
+        let path = layout
+            .cloned()
+            .unwrap_or_else(|| Layout::default_for(year))
+            .path(dir, year, day, false);
+        let client = match &mut client {
+            Some(client) => client,
+            None => client.insert(Client::new(&args.client)?),
+        };
+        match download(client, year, day, &path) {
+            Ok(()) => println!("Day {day}: downloaded to {}", path.display()),
+            Err(err) => {
+                println!("Day {day}: {err}");
+                failed = true;
+            }
+        }
+    }
+    if failed {
+        return Err(Box::from("failed to download some of the inputs"));
+    }
+    Ok(())
+}
+
+fn download(client: &mut Client, year: usize, day: usize, path: &Path) -> Result<(), Box<dyn Error>> {
+    let input = client.input(year, day)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    // Write to a temporary file first, so that an interrupted download is never
+    // mistaken for a cached input.
+    let tmp_path = path.with_extension("part");
+    fs::write(&tmp_path, input)?;
+    fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use std::{
+        env,
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        path::PathBuf,
+        thread::{self, JoinHandle},
+        time::{Duration, Instant},
+    };
+
+    const INPUT: &str = "1000\n2000\n";
+    const SESSION: &str = "test-session";
+    const THROTTLE: Duration = Duration::from_millis(300);
+
+    #[derive(Parser)]
+    struct TestArgs {
+        #[clap(flatten)]
+        fetch: FetchArgs,
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc_fetch_test_{name}_{}", std::process::id()));
+        _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn args(base_url: &str, dir: &Path) -> FetchArgs {
+        let session_file = dir.join("session");
+        fs::write(&session_file, format!("{SESSION}\n")).unwrap();
+        let throttle = THROTTLE.as_secs_f64().to_string();
+        TestArgs::parse_from([
+            "fetch",
+            "--base-url",
+            base_url,
+            "--session-file",
+            session_file.to_str().unwrap(),
+            "--throttle",
+            &throttle,
+        ])
+        .fetch
+    }
+
+    // A stand-in for the website, answering `count` requests with `INPUT` and
+    // returning when each request arrived along with its head.
+    fn serve(listener: TcpListener, count: usize) -> JoinHandle<Vec<(Instant, String)>> {
+        thread::spawn(move || {
+            (0..count)
+                .map(|_| {
+                    let (mut stream, _) = listener.accept().unwrap();
+                    let mut reader = BufReader::new(stream.try_clone().unwrap());
+                    let mut head = String::new();
+                    loop {
+                        let mut line = String::new();
+                        reader.read_line(&mut line).unwrap();
+                        if line.trim().is_empty() {
+                            break;
+                        }
+                        head.push_str(&line);
+                    }
+                    let arrived = Instant::now();
+                    write!(
+                        stream,
+                        "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{INPUT}",
+                        INPUT.len()
+                    )
+                    .unwrap();
+                    (arrived, head)
+                })
+                .collect()
+        })
+    }
+
+    fn header<'a>(head: &'a str, name: &str) -> Option<&'a str> {
+        head.lines().find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            key.eq_ignore_ascii_case(name).then(|| value.trim())
+        })
+    }
+
+    #[test]
+    fn downloads_missing_inputs() {
+        let dir = temp_dir("download");
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = serve(listener, 2);
+        run(&dir, 2022, &[1, 2], None, &args(&base_url, &dir)).unwrap();
+        let requests = server.join().unwrap();
+
+        // The session in the environment takes precedence over the file.
+        let session = env::var("AOC_SESSION").unwrap_or(SESSION.to_owned());
+        for (day, (_, head)) in [1, 2].into_iter().zip(&requests) {
+            assert!(head.starts_with(&format!("GET /2022/day/{day}/input ")), "{head}");
+            assert!(header(head, "User-Agent").is_some_and(|agent| agent.starts_with("adventofcode2022/")));
+            assert_eq!(header(head, "Cookie"), Some(format!("session={session}").as_str()));
+            assert_eq!(fs::read_to_string(dir.join(format!("{day}/input"))).unwrap(), INPUT);
+        }
+        // The throttle counts from when the first request was made, which is a
+        // little before it arrived.
+        let gap = requests[1].0 - requests[0].0;
+        assert!(
+            gap >= THROTTLE - Duration::from_millis(10),
+            "requests only {gap:?} apart"
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn never_downloads_existing_inputs() {
+        let dir = temp_dir("cached");
+        fs::create_dir_all(dir.join("1")).unwrap();
+        fs::write(dir.join("1/input"), "cached").unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        listener.set_nonblocking(true).unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        run(&dir, 2022, &[1], None, &args(&base_url, &dir)).unwrap();
+        assert!(listener.accept().is_err(), "an existing input was requested");
+        assert_eq!(fs::read_to_string(dir.join("1/input")).unwrap(), "cached");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
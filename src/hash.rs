@@ -0,0 +1,9 @@
+// A 64-bit FNV-1a hash as 16 hexadecimal digits, used to tell inputs apart in
+// the files kept between runs. Unlike `DefaultHasher`, it is guaranteed to stay
+// the same across Rust versions.
+pub(crate) fn hash(bytes: &[u8]) -> String {
+    let hash = bytes.iter().fold(0xcbf29ce484222325_u64, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+    format!("{hash:016x}")
+}
@@ -0,0 +1,241 @@
+use crate::*;
+use output::{Outcome, Record, TimingsRecord};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, error::Error, fs, path::Path, process, time::Duration};
+
+// A run of a day, as stored in the run history. Runs are keyed by the commit
+// and the hash of the input, so that the answers for the same input can be
+// compared across commits.
+#[derive(Serialize, Deserialize)]
+struct Run {
+    timestamp: u64,
+    commit: Option<String>,
+    year: usize,
+    day: usize,
+    input_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    params: BTreeMap<String, String>,
+    part_1: Option<Answer>,
+    part_2: Option<Answer>,
+    timings: Option<TimingsRecord>,
+    status: Outcome,
+}
+
+impl Run {
+    fn answers(&self) -> [&Option<Answer>; 2] {
+        [&self.part_1, &self.part_2]
+    }
+
+    fn parts(&self) -> [bool; 2] {
+        self.answers().map(Option::is_some)
+    }
+
+    // Whether any answer is different from the one in `other`, or the status is,
+    // ignoring the parts that were not solved in both runs.
+    fn changed_from(&self, other: &Run) -> bool {
+        self.status != other.status
+            || self
+                .answers()
+                .into_iter()
+                .zip(other.answers())
+                .any(|answers| matches!(answers, (Some(answer), Some(other)) if answer != other))
+    }
+}
+
+// The commit checked out in the current directory, marked as dirty if there are
+// uncommitted changes, or `None` outside of a git repository.
+fn git_commit() -> Option<String> {
+    let output = process::Command::new("git")
+        .args(["rev-parse", "--short=12", "HEAD"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let commit = String::from_utf8(output.stdout).ok()?.trim().to_owned();
+    let dirty = process::Command::new("git")
+        .args(["status", "--porcelain", "--untracked-files=no"])
+        .output()
+        .is_ok_and(|output| !output.stdout.is_empty());
+    Some(if dirty { format!("{commit}-dirty") } else { commit })
+}
+
+// Collects the runs of the days to append them to the run history at once.
+pub(crate) struct Recorder {
+    timestamp: u64,
+    commit: Option<String>,
+    runs: Vec<Run>,
+}
+
+impl Recorder {
+    pub fn new() -> Self {
+        Self {
+            timestamp: jsonl::now(),
+            commit: git_commit(),
+            runs: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, year: usize, record: &Record, input: &Input, params: &Params) {
+        let input_hash = match input {
+            Input::File(path) => fs::read(path).ok().map(|data| hash::hash(&data)),
+            Input::Stdin => None,
+            // A day without an input has nothing to compare across commits.
+            Input::Missing(_) => return,
+        };
+        self.runs.push(Run {
+            timestamp: self.timestamp,
+            commit: self.commit.clone(),
+            year,
+            day: record.day,
+            input_hash,
+            params: params
+                .iter()
+                .map(|(name, value)| (name.to_owned(), value.to_owned()))
+                .collect(),
+            part_1: record.part_1.clone(),
+            part_2: record.part_2.clone(),
+            timings: record.elapsed.map(TimingsRecord::from),
+            status: record.status,
+        });
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        jsonl::append(path, &self.runs)
+    }
+}
+
+fn show_answer(answer: &Option<Answer>) -> String {
+    match answer {
+        // Images don't fit in a table, but a hash still shows when they change.
+        Some(Answer::Image(lines)) => format!("image {}", &hash::hash(lines.join("\n").as_bytes())[..8]),
+        Some(answer) => answer.to_string(),
+        None => "-".to_owned(),
+    }
+}
+
+fn total_time(run: &Run) -> Option<Duration> {
+    run.timings
+        .as_ref()
+        .map(|timings| Duration::from_nanos(timings.total_ns))
+}
+
+// Prints the runs of every day, one table for each input and set of
+// parameters. Consecutive runs of the same commit with the same answers for the
+// same parts are shown as one row with the fastest time, and a row whose
+// answers differ from the ones before it is flagged, as that usually means a
+// solver broke.
+pub(crate) fn show(path: &Path, year: usize, days: &[usize]) -> Result<(), Box<dyn Error>> {
+    let runs = jsonl::read::<Run>(path, "run history")?;
+    if runs.is_empty() {
+        println!("No runs recorded in {}, use --record to record them", path.display());
+        return Ok(());
+    }
+    for &day in days {
+        let mut groups: Vec<Vec<&Run>> = Vec::new();
+        for run in runs.iter().filter(|run| (run.year, run.day) == (year, day)) {
+            match groups
+                .iter_mut()
+                .find(|group| (&group[0].input_hash, &group[0].params) == (&run.input_hash, &run.params))
+            {
+                Some(group) => group.push(run),
+                None => groups.push(vec![run]),
+            }
+        }
+        for group in groups {
+            let mut rows: Vec<(&Run, usize, Option<Duration>)> = Vec::new();
+            for run in group {
+                match rows.last_mut() {
+                    Some((last, count, time))
+                        if last.commit == run.commit && last.parts() == run.parts() && !run.changed_from(last) =>
+                    {
+                        *count += 1;
+                        *time = (*time).into_iter().chain(total_time(run)).min();
+                    }
+                    _ => rows.push((run, 1, total_time(run))),
+                }
+            }
+            let first = rows[0].0;
+            print!(
+                "Day {day}, input {}",
+                first.input_hash.as_deref().unwrap_or("from standard input")
+            );
+            if !first.params.is_empty() {
+                let params = first.params.iter().map(|(name, value)| format!("{name}={value}"));
+                print!(" with {}", params.collect::<Vec<_>>().join(", "));
+            }
+            println!();
+            let cells = rows
+                .iter()
+                .map(|(run, count, _)| {
+                    let commit = run.commit.as_deref().unwrap_or("unknown");
+                    let commit = if *count > 1 {
+                        format!("{commit} ({count}x)")
+                    } else {
+                        commit.to_owned()
+                    };
+                    let [part_1, part_2] = run.answers().map(show_answer);
+                    [commit, part_1, part_2]
+                })
+                .collect::<Vec<_>>();
+            let width = |i: usize, header: &str| {
+                cells
+                    .iter()
+                    .map(|cell| cell[i].len())
+                    .chain([header.len()])
+                    .max()
+                    .unwrap_or_default()
+            };
+            let widths = [width(0, "Commit"), width(1, "Part 1"), width(2, "Part 2")];
+            println!(
+                "  {:<w0$}  {:<w1$}  {:<w2$}  {:<7}  Time",
+                "Commit",
+                "Part 1",
+                "Part 2",
+                "Status",
+                w0 = widths[0],
+                w1 = widths[1],
+                w2 = widths[2]
+            );
+            // The latest answer to each part and status, to compare each row with.
+            let mut known: [Option<&Answer>; 2] = [None, None];
+            let mut last_status = None;
+            for (i, ((run, _, time), [commit, part_1, part_2])) in rows.iter().zip(&cells).enumerate() {
+                // Times are only comparable between runs that solved the same parts.
+                let previous = rows[..i]
+                    .iter()
+                    .rev()
+                    .find(|(previous, ..)| previous.parts() == run.parts());
+                let time = match (time, previous.and_then(|(_, _, time)| *time)) {
+                    (Some(time), Some(previous)) => {
+                        let change = (time.as_secs_f64() / previous.as_secs_f64() - 1.0) * 100.0;
+                        format!("{time:.2?} ({change:+.1}%)")
+                    }
+                    (Some(time), None) => format!("{time:.2?}"),
+                    (None, _) => "-".to_owned(),
+                };
+                let answers = run.answers().map(Option::as_ref);
+                let changed = last_status.is_some_and(|status| status != run.status)
+                    || answers
+                        .iter()
+                        .zip(known)
+                        .any(|answers| matches!(answers, (Some(answer), Some(known)) if *answer != known));
+                let flag = if changed { "  CHANGED" } else { "" };
+                for (known, answer) in known.iter_mut().zip(answers) {
+                    if answer.is_some() {
+                        *known = answer;
+                    }
+                }
+                last_status = Some(run.status);
+                println!(
+                    "  {commit:<w0$}  {part_1:<w1$}  {part_2:<w2$}  {:<7}  {time}{flag}",
+                    run.status.as_str(),
+                    w0 = widths[0],
+                    w1 = widths[1],
+                    w2 = widths[2]
+                );
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use crate::pool;
+use adventofcode2022::CancellationToken;
+use std::{
+    cell::{Cell, RefCell},
+    panic::{self, AssertUnwindSafe},
+    sync::mpsc,
+    thread,
+    time::Duration,
+};
+
+thread_local! {
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+    static LAST_PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+// Replaces the panic hook, so that panics caught by `catch_panic` are recorded
+// instead of printed. Any other panic is still handled by the default hook.
+pub(crate) fn install_hook() {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if CATCHING.with(Cell::get) {
+            LAST_PANIC.with(|last| *last.borrow_mut() = Some(info.to_string().replace('\n', " ")));
+        } else {
+            default_hook(info);
+        }
+    }));
+}
+
+// Runs `f`, turning a panic into an error message with the location of the
+// panic (if the hook is installed).
+pub(crate) fn catch_panic<R>(f: impl FnOnce() -> R) -> Result<R, String> {
+    CATCHING.with(|catching| catching.set(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.with(|catching| catching.set(false));
+    result.map_err(|payload| {
+        LAST_PANIC
+            .with(|last| last.borrow_mut().take())
+            .or_else(|| payload.downcast_ref::<&str>().map(|s| s.to_string()))
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "panicked".to_owned())
+    })
+}
+
+// Runs `f` on a new thread and waits at most `timeout` for its result. The
+// token passed to `f` is cancelled once the time is up, so that the thread can
+// stop cleanly instead of running on in the background.
+pub(crate) fn run_with_timeout<R, F>(timeout: Duration, f: F) -> Option<R>
+where
+    R: Send + 'static,
+    F: FnOnce(&CancellationToken) -> R + Send + 'static,
+{
+    let cancel = CancellationToken::default();
+    let (tx, rx) = mpsc::channel();
+    let token = cancel.clone();
+    thread::Builder::new()
+        .stack_size(pool::WORKER_STACK_SIZE)
+        .spawn(move || _ = tx.send(f(&token)))
+        .expect("failed to spawn worker thread");
+    let result = rx.recv_timeout(timeout).ok();
+    if result.is_none() {
+        cancel.cancel();
+    }
+    result
+}
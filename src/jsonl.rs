@@ -0,0 +1,47 @@
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    error::Error,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+// The current time in seconds since the Unix epoch, as stored in the files kept
+// between runs.
+pub(crate) fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+// Reads a file with a JSON value on every line, skipping empty lines, or returns
+// nothing if there is no such file yet. `what` names the file in errors.
+pub(crate) fn read<T: DeserializeOwned>(path: &Path, what: &str) -> Result<Vec<T>, Box<dyn Error>> {
+    if !path.is_file() {
+        return Ok(Vec::new());
+    }
+    fs::read_to_string(path)?
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str(line)
+                .map_err(|err| format!("malformed {what} {path:?} at line {}: {err}", i + 1).into())
+        })
+        .collect()
+}
+
+// Appends the values to a file with a JSON value on every line, creating it if
+// needed.
+pub(crate) fn append<'a, T: Serialize + 'a>(
+    path: &Path,
+    values: impl IntoIterator<Item = &'a T>,
+) -> Result<(), Box<dyn Error>> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    for value in values {
+        writeln!(file, "{}", serde_json::to_string(value)?)?;
+    }
+    Ok(())
+}
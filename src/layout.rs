@@ -0,0 +1,162 @@
+use std::{
+    error::Error,
+    fmt::Write,
+    path::{Path, PathBuf},
+};
+
+use adventofcode2022::DEFAULT_YEAR;
+
+// The layouts tried in order when no layout is given, the first one matching
+// an existing file is used for each day. Layouts without the year are only
+// used for the default year, as the inputs of other years can't be told apart.
+const AUTODETECT: &[&str] = &[
+    "{dir}/{year}/{day}/{input}",
+    "{dir}/{year}/{day:02}/{input}",
+    "{dir}/{year}/{day}/{input}.txt",
+    "{dir}/{year}/{day:02}/{input}.txt",
+    "{dir}/{year}/day{day:02}.txt",
+    "{dir}/{year}/day{day}.txt",
+    "{dir}/{year}/{day:02}.txt",
+    "{dir}/{year}/{day}.txt",
+    "{dir}/{day}/{input}",
+    "{dir}/{day:02}/{input}",
+    "{dir}/{day}/{input}.txt",
+    "{dir}/{day:02}/{input}.txt",
+    "{dir}/day{day:02}.txt",
+    "{dir}/day{day}.txt",
+    "{dir}/{day:02}.txt",
+    "{dir}/{day}.txt",
+];
+
+enum Segment {
+    Text(String),
+    Dir,
+    Year,
+    Day { width: usize },
+    Input,
+}
+
+// A template for the path of the input of a day, e.g. `{dir}/day{day:02}.txt`.
+// The placeholders are `{dir}` (the input directory), `{year}`, `{day}` (or
+// `{day:02}` to pad it with zeros to 2 digits) and `{input}` (`input`, or
+// `input_test` for the example input). Templates without `{input}` name the
+// example input by adding `_test` to the file name before the extension.
+#[derive(Clone)]
+pub(crate) struct Layout(String);
+
+impl Layout {
+    // The layout for new inputs, which is the layout of the inputs in this
+    // repository for the default year.
+    pub fn default_for(year: usize) -> Self {
+        Self::from(if year == DEFAULT_YEAR {
+            "{dir}/{day}/{input}"
+        } else {
+            "{dir}/{year}/{day}/{input}"
+        })
+    }
+
+    fn from(template: &str) -> Self {
+        Self(template.to_owned())
+    }
+
+    fn has_year(&self) -> bool {
+        self.0.contains("{year}")
+    }
+
+    pub fn parse(template: &str) -> Result<Self, String> {
+        Self::segments(template)?;
+        Ok(Self(template.to_owned()))
+    }
+
+    fn segments(template: &str) -> Result<Vec<Segment>, String> {
+        let mut segments = Vec::new();
+        let mut rest = template;
+        while let Some(start) = rest.find('{') {
+            let end = rest[start..]
+                .find('}')
+                .ok_or_else(|| format!("unclosed placeholder in layout {template:?}"))?;
+            segments.push(Segment::Text(rest[..start].to_owned()));
+            segments.push(match &rest[start + 1..start + end] {
+                "dir" => Segment::Dir,
+                "year" => Segment::Year,
+                "input" => Segment::Input,
+                "day" => Segment::Day { width: 0 },
+                placeholder => match placeholder.strip_prefix("day:0").map(str::parse) {
+                    Some(Ok(width)) => Segment::Day { width },
+                    _ => return Err(format!("unknown placeholder {{{placeholder}}} in layout {template:?}")),
+                },
+            });
+            rest = &rest[start + end + 1..];
+        }
+        segments.push(Segment::Text(rest.to_owned()));
+        Ok(segments)
+    }
+
+    pub fn path(&self, dir: &Path, year: usize, day: usize, example: bool) -> PathBuf {
+        let segments = Self::segments(&self.0).expect("layout is validated on creation");
+        let mut path = String::new();
+        let mut has_dir = false;
+        let mut has_input = false;
+        for segment in &segments {
+            match segment {
+                Segment::Text(text) => path.push_str(text),
+                Segment::Dir => {
+                    path.push_str(&dir.to_string_lossy());
+                    has_dir = true;
+                }
+                Segment::Year => _ = write!(path, "{year}"),
+                Segment::Day { width } => _ = write!(path, "{day:0width$}"),
+                Segment::Input => {
+                    path.push_str(if example { "input_test" } else { "input" });
+                    has_input = true;
+                }
+            }
+        }
+        let mut path = if has_dir { PathBuf::from(path) } else { dir.join(path) };
+        if example && !has_input {
+            let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+            let name = match path.extension() {
+                Some(extension) => format!("{stem}_test.{}", extension.to_string_lossy()),
+                None => format!("{stem}_test"),
+            };
+            path.set_file_name(name);
+        }
+        path
+    }
+}
+
+// Finds the input of a day in `dir`, using the given layout or the first of the
+// common layouts that matches an existing file.
+pub(crate) fn find_input(
+    dir: &Path,
+    year: usize,
+    day: usize,
+    example: bool,
+    layout: Option<&Layout>,
+) -> Result<PathBuf, Box<dyn Error>> {
+    let layouts = match layout {
+        Some(layout) => vec![layout.clone()],
+        None => AUTODETECT
+            .iter()
+            .map(|template| Layout::from(template))
+            .filter(|layout| year == DEFAULT_YEAR || layout.has_year())
+            .collect(),
+    };
+    let mut tried = Vec::new();
+    for layout in layouts {
+        let path = layout.path(dir, year, day, example);
+        if path.is_file() {
+            return Ok(path);
+        }
+        if !tried.contains(&path) {
+            tried.push(path);
+        }
+    }
+    let tried = tried
+        .iter()
+        .map(|path| format!("\n  {}", path.display()))
+        .collect::<String>();
+    Err(Box::from(format!(
+        "cannot find the input for day {day} of {year}, tried:{tried}"
+    )))
+}
@@ -0,0 +1,178 @@
+#![feature(array_zip)]
+#![feature(get_mut_unchecked)]
+#![feature(hash_drain_filter)]
+
+pub mod common;
+
+pub use common::{solve, Answer, CancellationToken, Param, Params, ParseError, Solution, Solver, SolverFn, Timings};
+use std::{error::Error, io::BufRead};
+
+/// The year of the puzzles used when no year is specified.
+pub const DEFAULT_YEAR: usize = 2022;
+
+/// The first year of the puzzles.
+pub const FIRST_YEAR: usize = 2015;
+
+/// The most days of the puzzles of any year.
+pub const MAX_DAYS: usize = 25;
+
+/// The number of days of the puzzles of a year.
+pub const fn days_in_year(year: usize) -> usize {
+    if year >= 2025 {
+        12
+    } else {
+        MAX_DAYS
+    }
+}
+
+// Declares the module of each day and registers its solver, keyed by the year
+// and the day. Days can be listed in any order, and days without a solution
+// are left out. The modules of a year other than 2022 live in their own
+// module, e.g. `2021 in y2021 => { 1 => day_01::Day01 }` for `y2021/day_01.rs`.
+macro_rules! solvers {
+    ($($year:literal $(in $ns:ident)? => { $($day:literal => $module:ident::$solver:ident),* $(,)? }),* $(,)?) => {
+        $(solvers!(@modules $($ns)? { $($module)* });)*
+
+        /// The solver and parameters of each day, by year.
+        pub static SOLVERS: &[(usize, &[(usize, SolverFn, &[Param])])] = &[
+            $(($year, solvers!(@solvers $($ns)? { $($day => $module::$solver),* }))),*
+        ];
+
+        // Every day is a valid day of its year, registered only once.
+        const _: () = {
+            let days = [$($(($year, $day)),*),*];
+            let mut i = 0;
+            while i < days.len() {
+                let (year, day) = days[i];
+                assert!(year >= FIRST_YEAR && day >= 1 && day <= days_in_year(year), "invalid day");
+                let mut j = i + 1;
+                while j < days.len() {
+                    assert!(year != days[j].0 || day != days[j].1, "day registered more than once");
+                    j += 1;
+                }
+                i += 1;
+            }
+        };
+    };
+    (@modules { $($module:ident)* }) => {
+        $(pub mod $module;)*
+    };
+    (@modules $ns:ident { $($module:ident)* }) => {
+        pub mod $ns {
+            $(pub mod $module;)*
+        }
+    };
+    (@solvers { $($day:literal => $module:ident::$solver:ident),* }) => {
+        &[$(($day, solve::<$module::$solver>, <$module::$solver as Solver>::PARAMS)),*]
+    };
+    (@solvers $ns:ident { $($day:literal => $module:ident::$solver:ident),* }) => {
+        &[$(($day, solve::<$ns::$module::$solver>, <$ns::$module::$solver as Solver>::PARAMS)),*]
+    };
+}
+
+solvers! {
+    2022 => {
+        1 => day_01::Day01,
+        2 => day_02::Day02,
+        3 => day_03::Day03,
+        4 => day_04::Day04,
+        5 => day_05::Day05,
+        6 => day_06::Day06,
+        7 => day_07::Day07,
+        8 => day_08::Day08,
+        9 => day_09::Day09,
+        10 => day_10::Day10,
+        11 => day_11::Day11,
+        12 => day_12::Day12,
+        13 => day_13::Day13,
+        14 => day_14::Day14,
+        15 => day_15::Day15,
+        16 => day_16::Day16,
+    },
+}
+
+fn year_solvers(year: usize) -> impl Iterator<Item = &'static (usize, SolverFn, &'static [Param])> {
+    SOLVERS
+        .iter()
+        .filter(move |(y, _)| *y == year)
+        .flat_map(|(_, solvers)| solvers.iter())
+}
+
+/// The solver of a day, or `None` if the day is not implemented yet.
+pub fn solver(year: usize, day: usize) -> Option<SolverFn> {
+    year_solvers(year)
+        .find(|(d, ..)| *d == day)
+        .map(|(_, solver, _)| *solver)
+}
+
+/// The parameters of the solver of a day, empty if it has none or no solver.
+pub fn params(year: usize, day: usize) -> &'static [Param] {
+    year_solvers(year)
+        .find(|(d, ..)| *d == day)
+        .map_or(&[], |(.., params)| params)
+}
+
+/// The years with a solution for any day, in order.
+pub fn years() -> Vec<usize> {
+    let mut years = SOLVERS.iter().map(|(year, _)| *year).collect::<Vec<_>>();
+    years.sort();
+    years.dedup();
+    years
+}
+
+/// The days of a year with a solution, in order.
+pub fn days(year: usize) -> Vec<usize> {
+    let mut days = year_solvers(year).map(|(day, ..)| *day).collect::<Vec<_>>();
+    days.sort();
+    days
+}
+
+/// Solves a day for the input read from `input`, only the given part if `part`
+/// is set, and returns the answers with the time each step took.
+pub fn solve_reader(
+    year: usize,
+    day: usize,
+    input: impl BufRead,
+    part: Option<usize>,
+) -> Result<(Solution, Timings), Box<dyn Error>> {
+    solve_cancellable(year, day, input, part, &CancellationToken::default())
+}
+
+/// Like `solve_reader`, but long-running solvers stop with an error once `cancel`
+/// is cancelled.
+pub fn solve_cancellable(
+    year: usize,
+    day: usize,
+    input: impl BufRead,
+    part: Option<usize>,
+    cancel: &CancellationToken,
+) -> Result<(Solution, Timings), Box<dyn Error>> {
+    solve_with_params(year, day, input, part, &Params::default(), cancel)
+}
+
+/// Like `solve_cancellable`, with the given values for the parameters of the day
+/// instead of their defaults.
+pub fn solve_with_params(
+    year: usize,
+    day: usize,
+    mut input: impl BufRead,
+    part: Option<usize>,
+    params: &Params,
+    cancel: &CancellationToken,
+) -> Result<(Solution, Timings), Box<dyn Error>> {
+    let solver = solver(year, day).ok_or_else(|| format!("day {day} of {year} is not implemented yet"))?;
+    solver(&mut input, part, params, cancel).map_err(|err| match err.downcast::<ParseError>() {
+        Ok(err) => Box::new(ParseError { day: Some(day), ..*err }),
+        Err(err) => err,
+    })
+}
+
+/// Like `solve_reader`, for an input that is already in memory.
+pub fn solve_str(
+    year: usize,
+    day: usize,
+    input: &str,
+    part: Option<usize>,
+) -> Result<(Solution, Timings), Box<dyn Error>> {
+    solve_reader(year, day, input.as_bytes(), part)
+}
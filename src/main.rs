@@ -1,47 +1,34 @@
-#![feature(array_zip)]
-#![feature(get_mut_unchecked)]
-#![feature(hash_drain_filter)]
-
-mod common;
-mod day_01;
-mod day_02;
-mod day_03;
-mod day_04;
-mod day_05;
-mod day_06;
-mod day_07;
-mod day_08;
-mod day_09;
-mod day_10;
-mod day_11;
-mod day_12;
-mod day_13;
-mod day_14;
-mod day_15;
-mod day_16;
-
-use clap::{CommandFactory, Parser};
-use common::*;
-use std::{collections::BTreeSet, error::Error, fs::File, io::Write, iter, path::Path};
-
-static SOLVERS: &[SolverFn] = &[
-    day_01::solve,
-    day_02::solve,
-    day_03::solve,
-    day_04::solve,
-    day_05::solve,
-    day_06::solve,
-    day_07::solve,
-    day_08::solve,
-    day_09::solve,
-    day_10::solve,
-    day_11::solve,
-    day_12::solve,
-    day_13::solve,
-    day_14::solve,
-    day_15::solve,
-    day_16::solve,
-];
+mod bench;
+mod cache;
+mod check;
+mod client;
+mod config;
+mod fetch;
+mod hash;
+mod history;
+mod isolate;
+mod jsonl;
+mod layout;
+mod output;
+mod pool;
+mod submit;
+mod watch;
+
+use adventofcode2022::*;
+use cache::{Cache, CacheCommand};
+use check::Answers;
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
+use config::Config;
+use layout::Layout;
+use output::{Format, Outcome, Output, Record};
+use std::{
+    collections::BTreeSet,
+    error::Error,
+    fs::{self, File},
+    io::{self, BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
 #[inline]
 fn capitalize_first_letter(s: &str) -> String {
@@ -49,12 +36,11 @@ fn capitalize_first_letter(s: &str) -> String {
 }
 
 fn error(err: Box<dyn Error>) -> ! {
-    _ = Args::command()
-        .error(
-            clap::error::ErrorKind::Format,
-            capitalize_first_letter(&err.to_string()),
-        )
-        .print();
+    let mut message = capitalize_first_letter(&err.to_string());
+    if let Some(err) = err.downcast_ref::<ParseError>() {
+        message = format!("{message}\n{}", err.snippet());
+    }
+    _ = Args::command().error(clap::error::ErrorKind::Format, message).print();
     _ = std::io::stdout().lock().flush();
     _ = std::io::stderr().lock().flush();
     std::process::exit(1)
@@ -67,19 +53,62 @@ fn parse_days(s: &str) -> Result<Days, String> {
     let mut days = BTreeSet::new();
     for part in s.split(',') {
         if let Some(range) = part.split_once('-') {
-            let start = clap_num::number_range(range.0, 1, SOLVERS.len())?;
-            let end = clap_num::number_range(range.1, 1, SOLVERS.len())?;
+            let start = clap_num::number_range(range.0, 1, MAX_DAYS)?;
+            let end = clap_num::number_range(range.1, 1, MAX_DAYS)?;
             for d in start..=end {
                 days.insert(d);
             }
         } else {
-            days.insert(clap_num::number_range(part, 1, SOLVERS.len())?);
+            days.insert(clap_num::number_range(part, 1, MAX_DAYS)?);
         }
     }
     Ok(Days(days.into_iter().collect()))
 }
 
-fn inputs_from_path(path: &str, days: &[usize]) -> Result<Vec<File>, Box<dyn Error>> {
+#[derive(Clone)]
+enum Input {
+    Stdin,
+    File(PathBuf),
+    // An input that cannot be found, with the reason why, which only fails its
+    // own day so that the other days still run.
+    Missing(String),
+}
+
+impl Input {
+    fn open(&self) -> Result<Box<dyn BufRead>, Box<dyn Error>> {
+        Ok(match self {
+            Input::Stdin => Box::new(io::stdin().lock()),
+            Input::File(path) => Box::new(BufReader::new(File::open(path)?)),
+            Input::Missing(err) => return Err(Box::from(err.as_str())),
+        })
+    }
+
+    fn answers(&self) -> Result<Answers, Box<dyn Error>> {
+        match self {
+            Input::Stdin | Input::Missing(_) => Ok(Default::default()),
+            Input::File(path) => Answers::read(&check::answers_path(path)),
+        }
+    }
+}
+
+// The input of each day, where a day whose input cannot be found is only reported
+// when it is solved.
+fn inputs_from_path(
+    path: &str,
+    year: usize,
+    days: &[usize],
+    example: bool,
+    layout: Option<&Layout>,
+) -> Result<Vec<Input>, Box<dyn Error>> {
+    if path == "-" {
+        if days.len() > 1 {
+            return Err(Box::from("cannot use standard input for multiple days"));
+        }
+        if example {
+            return Err(Box::from("cannot use standard input for the example input"));
+        }
+        return Ok(vec![Input::Stdin]);
+    }
     let p = Path::new(path);
     if !p.is_dir() && !p.is_file() {
         return Err(Box::from(format!("cannot access {path:?}: no such file or directory")));
@@ -88,42 +117,356 @@ fn inputs_from_path(path: &str, days: &[usize]) -> Result<Vec<File>, Box<dyn Err
         if days.len() > 1 {
             return Err(Box::from("cannot use a single input file for multiple days"));
         }
-        return iter::once(File::open(p))
-            .collect::<Result<Vec<_>, _>>()
-            .map_err(|e| e.into());
+        if example {
+            return Err(Box::from("cannot use a single input file for the example input"));
+        }
+        return Ok(vec![Input::File(p.to_path_buf())]);
     }
-    days.iter()
-        .map(|day| -> Result<_, Box<dyn Error>> {
-            let file_path = p.join(day.to_string()).join("input");
-            if !file_path.is_file() {
-                return Err(Box::from(format!("cannot open {file_path:?}: no such file")));
-            }
-            File::open(file_path).map_err(|e| e.into())
+    Ok(days
+        .iter()
+        .map(|&day| match layout::find_input(p, year, day, example, layout) {
+            Ok(path) => Input::File(path),
+            Err(err) => Input::Missing(err.to_string()),
         })
-        .collect()
+        .collect())
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the solver(s) repeatedly and report statistics on the time taken
+    Bench(bench::BenchArgs),
+    /// Download the puzzle input of the day(s) that do not have one yet
+    Fetch(fetch::FetchArgs),
+    /// Submit the answer to a part of the puzzle of a single day
+    Submit(submit::SubmitArgs),
+    /// List the parameters of the day(s) with their defaults
+    Params,
+    /// Show how the answers and times of the day(s) changed across commits
+    History,
+    /// Manage the cached answers
+    Cache {
+        #[clap(subcommand)]
+        command: CacheCommand,
+    },
 }
 
 #[derive(Parser)]
 struct Args {
+    #[clap(subcommand)]
+    command: Option<Command>,
+
+    /// The year of the puzzles
+    #[clap(long = "year", short = 'y', global = true)]
+    #[clap(default_value_t = DEFAULT_YEAR, value_parser = parse_year)]
+    year: usize,
+
     /// The day(s) (comma separated list including ranges with '-')
-    #[clap(long = "day", short = 'd')]
+    #[clap(long = "day", short = 'd', global = true)]
     #[clap(value_parser = parse_days)]
     days: Option<Days>,
 
-    /// The input directory (or file for a single day)
-    #[clap(long = "input", short = 'i')]
+    /// The input directory (or file or '-' for standard input for a single day)
+    #[clap(long = "input", short = 'i', global = true)]
     #[clap(default_value = "inputs")]
     input: String,
+
+    /// The layout of the input directory, e.g. '{dir}/day{day:02}.txt' (detected if not specified)
+    #[clap(long = "layout", short = 'l', global = true)]
+    #[clap(value_parser = Layout::parse)]
+    layout: Option<Layout>,
+
+    /// Set a parameter of the day(s) to a value, e.g. 'row=10' (see the params subcommand)
+    #[clap(long = "param", global = true)]
+    #[clap(value_parser = parse_param)]
+    params: Vec<(String, String)>,
+
+    /// Use the example input of each day instead of the puzzle input
+    #[clap(long = "example", short = 'e', global = true)]
+    example: bool,
+
+    /// Only solve the given part of the puzzle (1 or 2)
+    #[clap(long = "part", short = 'p', global = true)]
+    #[clap(value_parser = parse_part)]
+    part: Option<usize>,
+
+    /// Report the time taken to parse the input and to solve each part
+    #[clap(long = "time", short = 't')]
+    time: bool,
+
+    /// Check the answers against the expected answers stored next to the input
+    #[clap(long = "check", short = 'c')]
+    check: bool,
+
+    /// The output format
+    #[clap(long = "format", short = 'f', value_enum)]
+    #[clap(default_value = "text")]
+    format: Format,
+
+    /// The number of days to solve in parallel
+    #[clap(long = "jobs", short = 'j')]
+    #[clap(default_value = "1", value_parser = parse_jobs)]
+    jobs: usize,
+
+    /// The maximum time in seconds to spend on each day
+    #[clap(long = "timeout")]
+    #[clap(value_parser = parse_timeout)]
+    timeout: Option<Duration>,
+
+    /// Append the answers and times to the run history
+    #[clap(long = "record")]
+    record: bool,
+
+    /// The file with the run history
+    #[clap(long = "runs-file", global = true)]
+    #[clap(default_value = ".aoc_runs.jsonl")]
+    runs_file: PathBuf,
+
+    /// Reuse the answers of earlier runs of the same build on the same input
+    #[clap(long = "cache", overrides_with = "no_cache")]
+    cache: bool,
+
+    /// Solve every day even if the cache is enabled in the config
+    #[clap(long = "no-cache", overrides_with = "cache")]
+    no_cache: bool,
+
+    /// The directory with the cached answers
+    #[clap(long = "cache-dir", global = true)]
+    #[clap(default_value = ".aoc_cache")]
+    cache_dir: PathBuf,
+
+    /// Keep running and solve the day(s) again whenever an input file changes
+    #[clap(long = "watch", short = 'w')]
+    watch: bool,
 }
 
-fn main() {
-    let args = Args::parse();
-    let days = args.days.unwrap_or(Days((1..=SOLVERS.len()).collect())).0;
-    let inputs = inputs_from_path(&args.input, &days).unwrap_or_else(|err| error(err));
-    for (day, file) in days.into_iter().zip(inputs.into_iter()) {
-        let results = SOLVERS[day - 1](file).unwrap_or_else(|err| error(err));
+fn parse_year(s: &str) -> Result<usize, String> {
+    clap_num::number_range(s, FIRST_YEAR, 9999)
+}
+
+fn parse_param(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((name, value)) if !name.is_empty() => Ok((name.to_owned(), value.to_owned())),
+        _ => Err(format!("{s} is not of the form <name>=<value>")),
+    }
+}
+
+fn parse_part(s: &str) -> Result<usize, String> {
+    clap_num::number_range(s, 1, 2)
+}
+
+fn parse_jobs(s: &str) -> Result<usize, String> {
+    clap_num::number_range(s, 1, usize::MAX)
+}
+
+fn parse_timeout(s: &str) -> Result<Duration, String> {
+    let seconds = s.parse::<f64>().map_err(|err| err.to_string())?;
+    Duration::try_from_secs_f64(seconds)
+        .ok()
+        .filter(|timeout| !timeout.is_zero())
+        .ok_or_else(|| format!("{s} is not a positive number of seconds"))
+}
+
+// Splits the given parameters by day, as each day only accepts its own
+// parameters, making sure that every parameter is used by any of the days. The
+// given parameters override the ones set for each day in the config.
+fn params_by_day(
+    year: usize,
+    days: &[usize],
+    config: &Config,
+    given: &[(String, String)],
+) -> Result<Vec<Params>, Box<dyn Error>> {
+    if let Some((name, _)) = given.iter().find(|(name, _)| {
+        !days
+            .iter()
+            .any(|&day| params(year, day).iter().any(|param| param.name == name))
+    }) {
+        return Err(format!("none of the days has a parameter called {name}").into());
+    }
+    days.iter()
+        .map(|&day| {
+            let declared = params(year, day);
+            let mut params = Params::default();
+            for (name, value) in config.params(year, day) {
+                if !declared.iter().any(|param| param.name == name) {
+                    return Err(format!(
+                        "day {day} has no parameter called {name}, set in {}",
+                        config.path().display()
+                    )
+                    .into());
+                }
+                params.set(name, value);
+            }
+            for (name, value) in given {
+                if declared.iter().any(|param| param.name == name) {
+                    params.set(name.as_str(), value.as_str());
+                }
+            }
+            Ok(params)
+        })
+        .collect()
+}
+
+fn list_params(year: usize, days: &[usize]) {
+    for &day in days {
+        let params = params(year, day);
+        if params.is_empty() {
+            continue;
+        }
         println!("Day {day}");
-        println!("  Part 1: {}", results.0.to_string().replace('\n', "\n          "));
-        println!("  Part 2: {}", results.1.to_string().replace('\n', "\n          "));
+        let defaults = params
+            .iter()
+            .map(|param| format!("{}={}", param.name, param.default))
+            .collect::<Vec<_>>();
+        let width = defaults.iter().map(String::len).max().unwrap_or_default();
+        for (default, param) in defaults.iter().zip(params) {
+            println!("  {default:<width$}  {}", param.description);
+        }
+    }
+}
+
+fn solve(args: &Args, cache: Option<&Cache>, day: usize, input: &Input, params: &Params) -> Record {
+    let (year, part, with_timings, with_check) = (args.year, args.part, args.time, args.check);
+    let answers = match with_check.then(|| input.answers()).transpose() {
+        Ok(answers) => answers,
+        Err(err) => return Record::new(day, Err(err), with_timings, None),
+    };
+    // Standard input can only be read once, so it is never cached.
+    let cached = match (cache, input) {
+        (Some(cache), Input::File(path)) => match fs::read(path) {
+            Ok(data) => Some((cache, cache.key(year, day, part, params, &data))),
+            Err(err) => return Record::new(day, Err(err.into()), with_timings, None),
+        },
+        _ => None,
+    };
+    if let Some(solution) = cached.as_ref().and_then(|(cache, key)| cache.get(key)) {
+        return Record::cached(day, solution, answers.as_ref());
+    }
+    let input = input.clone();
+    let params = params.clone();
+    let run = move |cancel: &CancellationToken| match isolate::catch_panic(|| {
+        input
+            .open()
+            .and_then(|reader| solve_with_params(year, day, reader, part, &params, cancel))
+    }) {
+        Ok(result) => Record::new(day, result, with_timings, answers.as_ref()),
+        Err(message) => Record::panicked(day, message),
+    };
+    let record = match args.timeout {
+        Some(timeout) => isolate::run_with_timeout(timeout, run).unwrap_or_else(|| Record::timed_out(day, timeout)),
+        None => run(&CancellationToken::default()),
+    };
+    if let (Some((cache, key)), Outcome::Ok) = (cached, record.status) {
+        let solution = (record.part_1.clone(), record.part_2.clone());
+        if let Err(err) = cache.put(&key, &solution) {
+            eprintln!("Warning: cannot cache the answers of day {day}: {err}");
+        }
+    }
+    record
+}
+
+// Solves every day and prints the results, returning whether any day failed.
+fn run(args: &Args, days: &[usize], inputs: &[Input], params: &[Params]) -> Result<bool, Box<dyn Error>> {
+    let mut output = Output::new(args.format, args.time, args.check);
+    let mut recorder = args.record.then(history::Recorder::new);
+    let cache = (args.cache && !args.no_cache)
+        .then(|| Cache::open(&args.cache_dir))
+        .transpose()?;
+    let mut failed = false;
+    pool::map_ordered(
+        days.iter().zip(inputs).zip(params).collect(),
+        args.jobs,
+        |((day, input), params)| solve(args, cache.as_ref(), **day, input, params),
+        |record| {
+            failed |= record.failed();
+            if let Some(recorder) = &mut recorder {
+                let i = days
+                    .iter()
+                    .position(|&day| day == record.day)
+                    .expect("only selected days are solved");
+                recorder.push(args.year, &record, &inputs[i], &params[i]);
+            }
+            output.push(record);
+        },
+    );
+    output.finish()?;
+    if let Some(recorder) = recorder {
+        recorder.save(&args.runs_file)?;
+    }
+    Ok(failed)
+}
+
+fn main() {
+    let config = Config::find().unwrap_or_else(|err| error(err));
+    let args = Args::from_arg_matches(&config.apply(Args::command()).get_matches()).unwrap_or_else(|err| err.exit());
+    let year = args.year;
+    if let Some(Command::Cache { command }) = &args.command {
+        cache::run(&args.cache_dir, command).unwrap_or_else(|err| error(err));
+        return;
+    }
+    let days = args.days.clone().unwrap_or_else(|| Days(days(year))).0;
+    if let Some(day) = days.iter().find(|&&day| day > days_in_year(year)) {
+        error(format!("{year} only has {} days, not day {day}", days_in_year(year)).into());
+    }
+    if let Some(Command::Fetch(fetch_args)) = &args.command {
+        if args.example {
+            error(Box::from("cannot fetch the example input"));
+        }
+        fetch::run(Path::new(&args.input), year, &days, args.layout.as_ref(), fetch_args)
+            .unwrap_or_else(|err| error(err));
+        return;
+    }
+    if days.is_empty() {
+        error(format!("no day of {year} is implemented yet").into());
+    }
+    let missing = days
+        .iter()
+        .filter(|&&day| solver(year, day).is_none())
+        .map(|day| day.to_string())
+        .collect::<Vec<_>>();
+    match missing.len() {
+        0 => (),
+        1 => error(format!("day {} of {year} is not implemented yet", missing[0]).into()),
+        _ => error(format!("days {} of {year} are not implemented yet", missing.join(", ")).into()),
+    }
+    if let Some(Command::Params) = &args.command {
+        list_params(year, &days);
+        return;
+    }
+    if let Some(Command::History) = &args.command {
+        history::show(&args.runs_file, year, &days).unwrap_or_else(|err| error(err));
+        return;
+    }
+    let params = params_by_day(year, &days, &config, &args.params).unwrap_or_else(|err| error(err));
+    let inputs =
+        inputs_from_path(&args.input, year, &days, args.example, args.layout.as_ref()).unwrap_or_else(|err| error(err));
+    if let Some(Command::Bench(bench_args)) = &args.command {
+        bench::run(year, &days, inputs, &params, args.part, bench_args).unwrap_or_else(|err| error(err));
+        return;
+    }
+    if let Some(Command::Submit(submit_args)) = &args.command {
+        let (&[day], [input]) = (days.as_slice(), inputs.as_slice()) else {
+            error(Box::from("can only submit the answer of a single day"));
+        };
+        let part = args
+            .part
+            .unwrap_or_else(|| error(Box::from("the part to submit must be given with --part")));
+        if !submit::run(year, day, input, &params[0], part, submit_args).unwrap_or_else(|err| error(err)) {
+            std::process::exit(1);
+        }
+        return;
+    }
+    isolate::install_hook();
+    if args.watch {
+        if args.input == "-" {
+            error(Box::from("cannot watch standard input"));
+        }
+        watch::run(&inputs, || {
+            if let Err(err) = run(&args, &days, &inputs, &params) {
+                eprintln!("Error: {err}");
+            }
+        });
+    }
+    if run(&args, &days, &inputs, &params).unwrap_or_else(|err| error(err)) {
+        std::process::exit(1);
     }
 }
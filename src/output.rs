@@ -0,0 +1,330 @@
+use crate::*;
+use check::{Answers, Status};
+use serde::{Deserialize, Serialize};
+use std::{error::Error, time::Duration};
+
+#[derive(Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Outcome {
+    Ok,
+    Error,
+    Panic,
+    Timeout,
+}
+
+impl Outcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Outcome::Ok => "ok",
+            Outcome::Error => "error",
+            Outcome::Panic => "panic",
+            Outcome::Timeout => "timeout",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct TimingsRecord {
+    parse_ns: u64,
+    part_1_ns: Option<u64>,
+    part_2_ns: Option<u64>,
+    pub total_ns: u64,
+}
+
+impl From<Timings> for TimingsRecord {
+    fn from(timings: Timings) -> Self {
+        Self {
+            parse_ns: timings.parse.as_nanos() as u64,
+            part_1_ns: timings.part_1.map(|time| time.as_nanos() as u64),
+            part_2_ns: timings.part_2.map(|time| time.as_nanos() as u64),
+            total_ns: timings.total().as_nanos() as u64,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub(crate) struct Record {
+    pub day: usize,
+    pub part_1: Option<Answer>,
+    pub part_2: Option<Answer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timings: Option<TimingsRecord>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    check: Option<CheckRecord>,
+    pub status: Outcome,
+    error: Option<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    cached: bool,
+    #[serde(skip)]
+    parse_error: Option<ParseError>,
+    #[serde(skip)]
+    expected: [Option<Answer>; 2],
+    // The time taken, even if the timings are not reported.
+    #[serde(skip)]
+    pub elapsed: Option<Timings>,
+}
+
+#[derive(Serialize)]
+pub(crate) struct CheckRecord {
+    part_1: Option<Status>,
+    part_2: Option<Status>,
+}
+
+impl Record {
+    pub fn new(
+        day: usize,
+        result: Result<(Solution, Timings), Box<dyn Error>>,
+        with_timings: bool,
+        answers: Option<&Answers>,
+    ) -> Self {
+        match result {
+            Ok(((part_1, part_2), timings)) => Self {
+                day,
+                timings: with_timings.then(|| timings.into()),
+                check: answers.map(|answers| CheckRecord {
+                    part_1: part_1.as_ref().map(|answer| answers.check(1, answer)),
+                    part_2: part_2.as_ref().map(|answer| answers.check(2, answer)),
+                }),
+                status: Outcome::Ok,
+                error: None,
+                cached: false,
+                parse_error: None,
+                expected: [1, 2].map(|part| answers.and_then(|a| a.expected(part)).cloned()),
+                elapsed: Some(timings),
+                part_1,
+                part_2,
+            },
+            Err(err) => Self {
+                parse_error: err.downcast_ref::<ParseError>().cloned(),
+                ..Self::unsuccessful(day, Outcome::Error, err.to_string())
+            },
+        }
+    }
+
+    // The answers of a day from the cache, which has no timings as it was not
+    // solved.
+    pub fn cached(day: usize, solution: Solution, answers: Option<&Answers>) -> Self {
+        Self {
+            cached: true,
+            elapsed: None,
+            ..Self::new(day, Ok((solution, Timings::default())), false, answers)
+        }
+    }
+
+    pub fn panicked(day: usize, message: String) -> Self {
+        Self::unsuccessful(day, Outcome::Panic, message)
+    }
+
+    pub fn timed_out(day: usize, timeout: Duration) -> Self {
+        Self::unsuccessful(day, Outcome::Timeout, format!("no answer after {timeout:.2?}"))
+    }
+
+    fn unsuccessful(day: usize, status: Outcome, message: String) -> Self {
+        Self {
+            day,
+            part_1: None,
+            part_2: None,
+            timings: None,
+            check: None,
+            status,
+            error: Some(message),
+            cached: false,
+            parse_error: None,
+            expected: [None, None],
+            elapsed: None,
+        }
+    }
+
+    // The error message of a failed day, followed by the offending line of a
+    // parse error.
+    fn error_message(&self) -> Option<String> {
+        let message = self.error.as_ref()?;
+        Some(match &self.parse_error {
+            Some(err) => format!("{message}\n{}", err.snippet()),
+            None => message.clone(),
+        })
+    }
+
+    // Whether the day failed to run, or any answer did not match the expected answer.
+    pub fn failed(&self) -> bool {
+        self.status != Outcome::Ok
+            || self
+                .check
+                .as_ref()
+                .is_some_and(|check| check.part_1 == Some(Status::Fail) || check.part_2 == Some(Status::Fail))
+    }
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+pub(crate) struct Output {
+    format: Format,
+    with_timings: bool,
+    with_check: bool,
+    records: Vec<Record>,
+}
+
+impl Output {
+    pub fn new(format: Format, with_timings: bool, with_check: bool) -> Self {
+        if format == Format::Csv {
+            let mut header = vec!["day", "part_1", "part_2"];
+            if with_timings {
+                header.extend(["parse_ns", "part_1_ns", "part_2_ns", "total_ns"]);
+            }
+            if with_check {
+                header.extend(["check_1", "check_2"]);
+            }
+            header.extend(["status", "error"]);
+            println!("{}", header.join(","));
+        }
+        Self {
+            format,
+            with_timings,
+            with_check,
+            records: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, record: Record) {
+        match self.format {
+            Format::Text => {
+                println!("Day {}", record.day);
+                let statuses = record.check.as_ref().map(|check| [check.part_1, check.part_2]);
+                for (i, part) in [&record.part_1, &record.part_2].into_iter().enumerate() {
+                    let Some(answer) = part else {
+                        continue;
+                    };
+                    print!("  Part {}: {}", i + 1, answer.to_string().replace('\n', "\n          "));
+                    if let Some(status) = statuses.and_then(|statuses| statuses[i]) {
+                        match (status, &record.expected[i]) {
+                            (Status::Fail, Some(expected)) => {
+                                print!(
+                                    " ({status}, expected {})",
+                                    expected.to_string().replace('\n', "\n          ")
+                                )
+                            }
+                            _ => print!(" ({status})"),
+                        }
+                    }
+                    println!();
+                }
+                if let Some(message) = record.error_message() {
+                    let label = match record.status {
+                        Outcome::Panic => "Panic",
+                        Outcome::Timeout => "TIMEOUT",
+                        _ => "Error",
+                    };
+                    println!("  {label}: {}", message.replace('\n', "\n         "));
+                }
+                if record.cached && self.with_timings {
+                    println!("  Time:   cached");
+                }
+                if let Some(timings) = &record.timings {
+                    let mut stages = vec![format!("parse: {:.2?}", Duration::from_nanos(timings.parse_ns))];
+                    for (i, part_ns) in [timings.part_1_ns, timings.part_2_ns].into_iter().enumerate() {
+                        if let Some(ns) = part_ns {
+                            stages.push(format!("part {}: {:.2?}", i + 1, Duration::from_nanos(ns)));
+                        }
+                    }
+                    println!(
+                        "  Time:   {:.2?} ({})",
+                        Duration::from_nanos(timings.total_ns),
+                        stages.join(", ")
+                    );
+                }
+            }
+            Format::Csv => {
+                let optional = |value: Option<String>| value.unwrap_or_default();
+                let mut fields = vec![
+                    record.day.to_string(),
+                    csv_field(&optional(record.part_1.as_ref().map(|answer| answer.to_string()))),
+                    csv_field(&optional(record.part_2.as_ref().map(|answer| answer.to_string()))),
+                ];
+                if self.with_timings {
+                    match &record.timings {
+                        Some(timings) => fields.extend([
+                            timings.parse_ns.to_string(),
+                            optional(timings.part_1_ns.map(|ns| ns.to_string())),
+                            optional(timings.part_2_ns.map(|ns| ns.to_string())),
+                            timings.total_ns.to_string(),
+                        ]),
+                        None => fields.extend(vec![String::new(); 4]),
+                    }
+                }
+                if self.with_check {
+                    match &record.check {
+                        Some(check) => fields.extend([
+                            optional(check.part_1.map(|status| status.to_string())),
+                            optional(check.part_2.map(|status| status.to_string())),
+                        ]),
+                        None => fields.extend(vec![String::new(); 2]),
+                    }
+                }
+                fields.push(record.status.as_str().to_owned());
+                fields.push(csv_field(record.error.as_deref().unwrap_or_default()));
+                println!("{}", fields.join(","));
+            }
+            Format::Json => (),
+        }
+        self.records.push(record);
+    }
+
+    fn print_summary(&self) {
+        println!("Summary");
+        println!("  {:<5} {:<7} Error", "Day", "Status");
+        for record in &self.records {
+            let message = record.error.as_deref().unwrap_or_default();
+            let line = format!(
+                "  {:<5} {:<7} {}",
+                record.day,
+                record.status.as_str(),
+                message.lines().next().unwrap_or_default()
+            );
+            println!("{}", line.trim_end());
+        }
+        let count = |status| self.records.iter().filter(|record| record.status == status).count();
+        println!(
+            "  {} ok, {} error(s), {} panic(s), {} timeout(s)",
+            count(Outcome::Ok),
+            count(Outcome::Error),
+            count(Outcome::Panic),
+            count(Outcome::Timeout)
+        );
+    }
+
+    pub fn finish(self) -> Result<(), Box<dyn Error>> {
+        match self.format {
+            Format::Text => {
+                // A single day that succeeded needs no summary, its answers say it all.
+                if self.records.len() > 1 || self.records.iter().any(|record| record.status != Outcome::Ok) {
+                    self.print_summary();
+                }
+                if self.with_timings {
+                    let total_time: Duration = self
+                        .records
+                        .iter()
+                        .filter_map(|record| record.timings.as_ref())
+                        .map(|timings| Duration::from_nanos(timings.total_ns))
+                        .sum();
+                    println!("Total time: {total_time:.2?}");
+                }
+            }
+            Format::Json => println!("{}", serde_json::to_string_pretty(&self.records)?),
+            Format::Csv => (),
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,55 @@
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
+
+// Worker threads get the same stack size as the main thread usually has, as
+// some of the solvers recurse quite deeply.
+pub(crate) const WORKER_STACK_SIZE: usize = 8 * 1024 * 1024;
+
+// Applies `f` to every item using `jobs` worker threads and passes the results
+// to `consume` in the original order of the items, as soon as they (and all the
+// results before them) are available.
+pub(crate) fn map_ordered<T, R, F, C>(items: Vec<T>, jobs: usize, f: F, mut consume: C)
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+    C: FnMut(R),
+{
+    if jobs <= 1 {
+        items.iter().map(f).for_each(consume);
+        return;
+    }
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(items.len()) {
+            let tx = tx.clone();
+            let (items, next, f) = (&items, &next, &f);
+            thread::Builder::new()
+                .stack_size(WORKER_STACK_SIZE)
+                .spawn_scoped(scope, move || loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    if i >= items.len() || tx.send((i, f(&items[i]))).is_err() {
+                        break;
+                    }
+                })
+                .expect("failed to spawn worker thread");
+        }
+        drop(tx);
+        let mut pending = BTreeMap::new();
+        let mut current = 0;
+        for (i, result) in rx {
+            pending.insert(i, result);
+            while let Some(result) = pending.remove(&current) {
+                consume(result);
+                current += 1;
+            }
+        }
+    });
+}
@@ -0,0 +1,337 @@
+use crate::*;
+use client::{Client, ClientArgs};
+use serde::{Deserialize, Serialize};
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    path::PathBuf,
+    time::Duration,
+};
+
+#[derive(clap::Args)]
+pub(crate) struct SubmitArgs {
+    /// The answer to submit (the puzzle is solved to get the answer if not specified)
+    answer: Option<String>,
+
+    /// The file with the history of all submissions
+    #[clap(long = "history")]
+    #[clap(default_value = ".aoc_submissions.jsonl")]
+    history: PathBuf,
+
+    #[clap(flatten)]
+    client: ClientArgs,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Verdict {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    RateLimited,
+    AlreadySolved,
+    Unknown,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::Incorrect => write!(f, "incorrect"),
+            Verdict::TooHigh => write!(f, "incorrect (too high)"),
+            Verdict::TooLow => write!(f, "incorrect (too low)"),
+            Verdict::RateLimited => write!(f, "rate limited"),
+            Verdict::AlreadySolved => write!(f, "already solved"),
+            Verdict::Unknown => write!(f, "unknown response"),
+        }
+    }
+}
+
+// Parses the wait time in a message like "You have 1m 23s left to wait" after
+// answering too soon, or like "please wait one minute before trying again"
+// after a wrong answer.
+fn parse_wait(page: &str) -> Option<Duration> {
+    match page.split_once("You have ") {
+        Some((_, rest)) => parse_time_left(rest.split_once(" left to wait")?.0),
+        None => parse_lockout(page.split_once(" before trying again")?.0.rsplit_once("wait ")?.1),
+    }
+}
+
+// Parses a time like "1m 23s".
+fn parse_time_left(wait: &str) -> Option<Duration> {
+    let mut seconds = 0;
+    for part in wait.split_whitespace() {
+        let (n, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+        seconds += n.parse::<u64>().ok()?
+            * match unit {
+                "h" => 3600,
+                "m" => 60,
+                "s" => 1,
+                _ => return None,
+            };
+    }
+    Some(Duration::from_secs(seconds))
+}
+
+// Parses a time like "one minute" or "5 minutes".
+fn parse_lockout(wait: &str) -> Option<Duration> {
+    let (n, unit) = wait.split_once(' ')?;
+    let n = match n {
+        "one" => 1,
+        n => n.parse::<u64>().ok()?,
+    };
+    let unit = match unit.trim_end_matches('s') {
+        "hour" => 3600,
+        "minute" => 60,
+        "second" => 1,
+        _ => return None,
+    };
+    Some(Duration::from_secs(n * unit))
+}
+
+fn parse_response(page: &str) -> (Verdict, Option<Duration>) {
+    if page.contains("That's the right answer") {
+        (Verdict::Correct, None)
+    } else if page.contains("You gave an answer too recently") {
+        (Verdict::RateLimited, parse_wait(page))
+    } else if page.contains("That's not the right answer") {
+        let verdict = if page.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if page.contains("your answer is too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Incorrect
+        };
+        (verdict, parse_wait(page))
+    } else if page.contains("You don't seem to be solving the right level") {
+        (Verdict::AlreadySolved, None)
+    } else {
+        (Verdict::Unknown, None)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Submission {
+    year: usize,
+    day: usize,
+    part: usize,
+    answer: Answer,
+    verdict: Verdict,
+    wait_secs: Option<u64>,
+    timestamp: u64,
+}
+
+fn as_integer(answer: &Answer) -> Option<i128> {
+    match answer {
+        Answer::Unsigned(n) => Some(*n as i128),
+        Answer::Signed(n) => Some(*n as i128),
+        _ => None,
+    }
+}
+
+// Checks the answer against the earlier submissions for the same part, to
+// avoid submitting an answer that is already known to be wrong.
+fn check_history(history: &[Submission], answer: &Answer) -> Result<(), Box<dyn Error>> {
+    for submission in history {
+        match submission.verdict {
+            Verdict::Correct if submission.answer == *answer => {
+                return Err(format!("{answer} was already submitted and is correct").into())
+            }
+            Verdict::Correct => return Err(format!("this part was already solved with {}", submission.answer).into()),
+            Verdict::Incorrect | Verdict::TooHigh | Verdict::TooLow if submission.answer == *answer => {
+                return Err(format!("{answer} was already submitted and is {}", submission.verdict).into())
+            }
+            _ => (),
+        }
+    }
+    // Both answering too soon and a wrong answer lock out further answers for a
+    // while.
+    let wait_until = history
+        .iter()
+        .filter_map(|submission| Some(submission.timestamp + submission.wait_secs?))
+        .max();
+    if let Some(wait_until) = wait_until.filter(|&wait_until| wait_until > jsonl::now()) {
+        return Err(format!(
+            "locked out after the last answer, wait another {}s",
+            wait_until - jsonl::now()
+        )
+        .into());
+    }
+    let Some(n) = as_integer(answer) else {
+        return Ok(());
+    };
+    let bound = |verdict| {
+        history
+            .iter()
+            .filter(move |submission| submission.verdict == verdict)
+            .filter_map(|submission| as_integer(&submission.answer))
+    };
+    if let Some(too_high) = bound(Verdict::TooHigh).min().filter(|&too_high| n >= too_high) {
+        return Err(format!("{answer} is not below {too_high}, which is too high").into());
+    }
+    if let Some(too_low) = bound(Verdict::TooLow).max().filter(|&too_low| n <= too_low) {
+        return Err(format!("{answer} is not above {too_low}, which is too low").into());
+    }
+    Ok(())
+}
+
+// Submits the answer of a part of a day and returns whether it was correct.
+pub(crate) fn run(
+    year: usize,
+    day: usize,
+    input: &Input,
+    params: &Params,
+    part: usize,
+    args: &SubmitArgs,
+) -> Result<bool, Box<dyn Error>> {
+    let answer = match &args.answer {
+        Some(answer) => answer.parse::<Answer>()?,
+        None => {
+            let cancel = CancellationToken::default();
+            let ((part_1, part_2), _) = solve_with_params(year, day, input.open()?, Some(part), params, &cancel)?;
+            if part == 1 { part_1 } else { part_2 }.ok_or("the puzzle has no answer for this part")?
+        }
+    };
+    if let Answer::Image(_) = answer {
+        return Err(Box::from(
+            "cannot submit a multi-line answer, submit the letters it shows as the answer instead",
+        ));
+    }
+    let history = jsonl::read::<Submission>(&args.history, "history file")?
+        .into_iter()
+        .filter(|submission| (submission.year, submission.day, submission.part) == (year, day, part))
+        .collect::<Vec<_>>();
+    check_history(&history, &answer).map_err(|err| format!("refusing to submit: {err}"))?;
+
+    let mut client = Client::new(&args.client)?;
+    let page = client.submit(year, day, part, &answer.to_string())?;
+    let (verdict, wait) = parse_response(&page);
+    jsonl::append(
+        &args.history,
+        [&Submission {
+            year,
+            day,
+            part,
+            answer: answer.clone(),
+            verdict,
+            wait_secs: wait.map(|wait| wait.as_secs()),
+            timestamp: jsonl::now(),
+        }],
+    )?;
+    print!("Day {day}, part {part}: {answer} is {verdict}");
+    match wait {
+        Some(wait) => println!(", wait {}s before submitting again", wait.as_secs()),
+        None => println!(),
+    }
+    Ok(verdict == Verdict::Correct)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CORRECT: &str = "<article><p>That's the right answer! You are one gold star closer to collecting enough \
+        star fruit. [<a href=\"/2022/day/1#part2\">Continue to Part Two</a>]</p></article>";
+    const TOO_HIGH: &str = "<article><p>That's not the right answer; your answer is too high.  If you're stuck, make \
+        sure you're using the full input data; there are also some general tips on the <a href=\"/2022/about\">about \
+        page</a>, or you can ask for hints on the <a href=\"https://www.reddit.com/r/adventofcode/\" \
+        target=\"_blank\">subreddit</a>.  Please wait one minute before trying again. [<a href=\"/2022/day/1\">Return \
+        to Day 1</a>]</p></article>";
+    const TOO_LOW: &str = "<article><p>That's not the right answer; your answer is too low.  If you're stuck, make \
+        sure you're using the full input data.  Because you have guessed incorrectly 4 times on this puzzle, please \
+        wait 5 minutes before trying again. [<a href=\"/2022/day/1\">Return to Day 1</a>]</p></article>";
+    const RATE_LIMITED: &str = "<article><p>You gave an answer too recently; you have to wait after submitting an \
+        answer before trying again.  You have 1m 23s left to wait. [<a href=\"/2022/day/1\">Return to Day \
+        1</a>]</p></article>";
+    const ALREADY_SOLVED: &str = "<article><p>You don't seem to be solving the right level.  Did you already complete \
+        it? [<a href=\"/2022/day/1\">Return to Day 1</a>]</p></article>";
+
+    #[test]
+    fn parses_responses() {
+        assert_eq!(parse_response(CORRECT), (Verdict::Correct, None));
+        assert_eq!(
+            parse_response(TOO_HIGH),
+            (Verdict::TooHigh, Some(Duration::from_secs(60)))
+        );
+        assert_eq!(
+            parse_response(TOO_LOW),
+            (Verdict::TooLow, Some(Duration::from_secs(300)))
+        );
+        assert_eq!(
+            parse_response(RATE_LIMITED),
+            (Verdict::RateLimited, Some(Duration::from_secs(83)))
+        );
+        assert_eq!(parse_response(ALREADY_SOLVED), (Verdict::AlreadySolved, None));
+        assert_eq!(parse_response("<html></html>"), (Verdict::Unknown, None));
+    }
+
+    #[test]
+    fn parses_wait_times() {
+        assert_eq!(parse_wait("You have 38s left to wait."), Some(Duration::from_secs(38)));
+        assert_eq!(
+            parse_wait("You have 1h 2m 3s left to wait."),
+            Some(Duration::from_secs(3723))
+        );
+        assert_eq!(
+            parse_wait("Please wait one minute before trying again."),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(
+            parse_wait("please wait 10 minutes before trying again."),
+            Some(Duration::from_secs(600))
+        );
+        assert_eq!(parse_wait("That's the right answer!"), None);
+    }
+
+    fn submission(answer: impl Into<Answer>, verdict: Verdict, wait_secs: Option<u64>, age: u64) -> Submission {
+        Submission {
+            year: 2022,
+            day: 1,
+            part: 1,
+            answer: answer.into(),
+            verdict,
+            wait_secs,
+            timestamp: jsonl::now() - age,
+        }
+    }
+
+    fn refusal(history: &[Submission], answer: impl Into<Answer>) -> String {
+        check_history(history, &answer.into()).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn refuses_a_solved_part() {
+        let history = [submission(42, Verdict::Correct, None, 3600)];
+        assert_eq!(refusal(&history, 42), "42 was already submitted and is correct");
+        assert_eq!(refusal(&history, 43), "this part was already solved with 42");
+    }
+
+    #[test]
+    fn refuses_a_rejected_answer() {
+        let history = [submission("abc", Verdict::Incorrect, None, 3600)];
+        assert_eq!(refusal(&history, "abc"), "abc was already submitted and is incorrect");
+        assert!(check_history(&history, &"abd".into()).is_ok());
+    }
+
+    #[test]
+    fn refuses_answers_outside_the_bounds() {
+        let history = [
+            submission(100, Verdict::TooHigh, Some(60), 3600),
+            submission(10, Verdict::TooLow, Some(60), 3600),
+        ];
+        assert_eq!(refusal(&history, 150), "150 is not below 100, which is too high");
+        assert_eq!(refusal(&history, 5), "5 is not above 10, which is too low");
+        assert!(check_history(&history, &50.into()).is_ok());
+    }
+
+    #[test]
+    fn refuses_while_locked_out() {
+        let rate_limited = [submission(1, Verdict::RateLimited, Some(83), 10)];
+        assert!(refusal(&rate_limited, 2).starts_with("locked out after the last answer"));
+        let wrong_answer = [submission(1, Verdict::Incorrect, Some(60), 10)];
+        assert!(refusal(&wrong_answer, 2).starts_with("locked out after the last answer"));
+        let expired = [submission(1, Verdict::Incorrect, Some(60), 61)];
+        assert!(check_history(&expired, &2.into()).is_ok());
+    }
+}
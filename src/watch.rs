@@ -0,0 +1,60 @@
+use crate::Input;
+use std::{
+    fs,
+    io::{self, Write},
+    path::Path,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+// How often the inputs are checked for changes. Polling keeps this portable,
+// without depending on the file system notifications of any platform.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+// The state of a file used to tell whether it changed, which includes the size
+// as the modification time may not change between two quick edits.
+type Stamp = Option<(SystemTime, u64)>;
+
+fn stamp(path: &Path) -> Stamp {
+    let metadata = fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+fn clear_screen() {
+    print!("\x1b[2J\x1b[H");
+    _ = io::stdout().flush();
+}
+
+// Calls `f` once, and then again every time any of the input files is modified,
+// clearing the screen before each call. This never returns.
+pub(crate) fn run(inputs: &[Input], mut f: impl FnMut()) -> ! {
+    let paths = inputs
+        .iter()
+        .filter_map(|input| match input {
+            Input::File(path) => Some(path.as_path()),
+            Input::Stdin | Input::Missing(_) => None,
+        })
+        .collect::<Vec<_>>();
+    let poll = || paths.iter().map(|path| stamp(path)).collect::<Vec<_>>();
+    let mut stamps = poll();
+    loop {
+        clear_screen();
+        f();
+        println!(
+            "\nWatching {} input file(s) for changes, press Ctrl+C to stop",
+            paths.len()
+        );
+        // Wait for the files to stay the same for one more poll after a change,
+        // so that a file being saved is not read half-written.
+        let mut current = stamps.clone();
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            let next = poll();
+            if next == current && next != stamps {
+                break;
+            }
+            current = next;
+        }
+        stamps = current;
+    }
+}
@@ -0,0 +1,80 @@
+use adventofcode2022::{solve_str, solve_with_params, Answer, CancellationToken, Params};
+use std::{fs, path::Path};
+
+// Reads the answers in the format of the text output, where further lines of a
+// multi-line answer follow the `Part` line they belong to.
+fn expected(answers: &str) -> [Option<Answer>; 2] {
+    let mut parts: [Option<String>; 2] = [None, None];
+    let mut current = None;
+    for line in answers.lines() {
+        match line.strip_prefix("Part ").and_then(|line| line.split_once(": ")) {
+            Some((part, answer)) => {
+                let i = part.parse::<usize>().unwrap() - 1;
+                parts[i] = Some(answer.to_owned());
+                current = Some(i);
+            }
+            None => {
+                let part = parts[current.unwrap()].as_mut().unwrap();
+                part.push('\n');
+                part.push_str(line);
+            }
+        }
+    }
+    parts.map(|part| part.map(|part| part.parse().unwrap()))
+}
+
+macro_rules! days {
+    ($($name:ident => $day:literal),* $(,)?) => {
+        $(
+            #[test]
+            fn $name() {
+                let input = include_str!(concat!("../inputs/", $day, "/input"));
+                let answers = include_str!(concat!("../inputs/", $day, "/answers"));
+                let ((part_1, part_2), _) = solve_str(2022, $day, input, None).unwrap();
+                assert_eq!([part_1, part_2], expected(answers));
+            }
+        )*
+    };
+}
+
+days! {
+    day_01 => 1,
+    day_02 => 2,
+    day_03 => 3,
+    day_04 => 4,
+    day_05 => 5,
+    day_06 => 6,
+    day_07 => 7,
+    day_08 => 8,
+    day_09 => 9,
+    day_10 => 10,
+    day_11 => 11,
+    day_12 => 12,
+    day_13 => 13,
+    day_14 => 14,
+    day_15 => 15,
+}
+
+#[test]
+fn examples() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("inputs");
+    for day in 1..=15 {
+        let input = fs::read_to_string(dir.join(format!("{day}/input_test"))).unwrap();
+        let answers = fs::read_to_string(dir.join(format!("{day}/answers_test"))).unwrap();
+        // Day 15 is the only day with parameters that differ for the example.
+        let params = match day {
+            15 => [("row", "10"), ("bound", "20")].into_iter().collect(),
+            _ => Params::default(),
+        };
+        let ((part_1, part_2), _) = solve_with_params(
+            2022,
+            day,
+            input.as_bytes(),
+            None,
+            &params,
+            &CancellationToken::default(),
+        )
+        .unwrap();
+        assert_eq!([part_1, part_2], expected(&answers), "day {day}");
+    }
+}